@@ -0,0 +1,83 @@
+// Compara la búsqueda exhaustiva de pares de asteroides en contacto con la
+// rejilla espacial, con miles de asteroides. Se ejecuta con `cargo bench`.
+use std::time::{Duration, Instant};
+
+use bubbleroid::constants::{WINDOW_HEIGHT, WINDOW_WIDTH};
+use bubbleroid::{wrapped_distance, SpatialGrid};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+const RUNS: usize = 5;
+
+struct Body {
+    pos: (f32, f32),
+    size: f32,
+}
+
+// Con la distancia que da la vuelta por los bordes, como en el juego
+fn touching(a: &Body, b: &Body) -> bool {
+    wrapped_distance(a.pos, b.pos) < a.size + b.size
+}
+
+fn brute_force(bodies: &[Body]) -> usize {
+    let mut pairs = 0;
+    for i in 0..bodies.len() {
+        for j in (i + 1)..bodies.len() {
+            pairs += touching(&bodies[i], &bodies[j]) as usize;
+        }
+    }
+    pairs
+}
+
+fn with_grid(grid: &mut SpatialGrid, bodies: &[Body]) -> usize {
+    let max_size = bodies.iter().map(|body| body.size).fold(0.0, f32::max);
+    grid.build(2.0 * max_size, bodies.iter().map(|body| body.pos));
+
+    let mut pairs = 0;
+    for (i, body) in bodies.iter().enumerate() {
+        grid.query(body.pos, body.size + max_size, |j| {
+            if j > i {
+                pairs += touching(body, &bodies[j]) as usize;
+            }
+        });
+    }
+    pairs
+}
+
+// Mediana de varias ejecuciones, junto con el resultado
+fn time(mut f: impl FnMut() -> usize) -> (Duration, usize) {
+    let mut result = 0;
+    let mut times: Vec<Duration> = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            result = f();
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    (times[RUNS / 2], result)
+}
+
+fn main() {
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+    let mut grid = SpatialGrid::default();
+
+    println!("{:>8} {:>14} {:>14} {:>8}", "n", "exhaustiva", "rejilla", "pares");
+    for n in [500, 1000, 2000, 5000, 10000] {
+        // Mismo rango de tamaños que la configuración por defecto, pero más pequeños
+        // cuantos más hay, para que quepan en la pantalla sin solaparse todos
+        let scale = (1000.0 / n as f32).sqrt().min(1.0);
+        let bodies: Vec<Body> = (0..n)
+            .map(|_| Body {
+                pos: (rng.gen_range(0.0..WINDOW_WIDTH), rng.gen_range(0.0..WINDOW_HEIGHT)),
+                size: rng.gen_range(15.0..40.0) * scale,
+            })
+            .collect();
+
+        let (brute_time, brute_pairs) = time(|| brute_force(&bodies));
+        let (grid_time, grid_pairs) = time(|| with_grid(&mut grid, &bodies));
+        assert_eq!(brute_pairs, grid_pairs, "la rejilla tiene que encontrar los mismos pares");
+
+        println!("{:>8} {:>14.2?} {:>14.2?} {:>8}", n, brute_time, grid_time, grid_pairs);
+    }
+}
@@ -0,0 +1,35 @@
+use rand::Rng;
+
+use crate::entities::Asteroid;
+use crate::physics::{wrap_position, wrapped_delta};
+
+// Un solo asteroide con la masa, el momento lineal y el momento angular de
+// los dos que se funden. Queda en su centro de masas con una forma irregular
+// nueva; el tamaño sale de sumar las masas, que van con el cuadrado del tamaño.
+pub fn merge(first: &Asteroid, second: &Asteroid, jaggedness: f32, rng: &mut impl Rng) -> Asteroid {
+    let total_mass = first.mass + second.mass;
+    let delta = wrapped_delta(first.pos, second.pos);
+    let center = (delta.0 * second.mass / total_mass, delta.1 * second.mass / total_mass);
+    let vel = (
+        (first.mass * first.vel.0 + second.mass * second.vel.0) / total_mass,
+        (first.mass * first.vel.1 + second.mass * second.vel.1) / total_mass,
+    );
+
+    // Momento angular respecto al centro de masas: el giro de cada uno más el
+    // de su movimiento alrededor del otro
+    let orbital = |asteroid: &Asteroid, r: (f32, f32)| {
+        let v = (asteroid.vel.0 - vel.0, asteroid.vel.1 - vel.1);
+        asteroid.mass * (r.0 * v.1 - r.1 * v.0)
+    };
+    let angular_momentum = first.inertia * first.angular_vel
+        + second.inertia * second.angular_vel
+        + orbital(first, (-center.0, -center.1))
+        + orbital(second, (delta.0 - center.0, delta.1 - center.1));
+
+    let size = (first.size * first.size + second.size * second.size).sqrt();
+    let pos = wrap_position((first.pos.0 + center.0, first.pos.1 + center.1));
+    let mut merged = Asteroid::jagged(pos, vel, size, jaggedness, rng);
+    merged.angle = if first.mass >= second.mass { first.angle } else { second.angle };
+    merged.angular_vel = angular_momentum / merged.inertia;
+    merged
+}
@@ -0,0 +1,461 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+// Parámetros de ajuste del juego. Todos los campos tienen valor por defecto,
+// así que un fichero de configuración solo necesita los que quiera cambiar.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub physics: PhysicsConfig,
+    pub player: PlayerConfig,
+    pub asteroids: AsteroidConfig,
+    pub bullets: BulletConfig,
+    pub particles: ParticleConfig,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PhysicsConfig {
+    pub gravity_constant: f32,
+    pub gravity_scale: f32,
+    // Multiplicador extra de la gravedad que siente la nave
+    pub player_gravity_factor: f32,
+    pub spring_constant: f32,
+    pub damping: f32,
+    // Deformación máxima como fracción del radio
+    pub max_deformation: f32,
+    pub gravity_solver: GravitySolver,
+    // Con menos asteroides que esto se usa el cálculo exacto
+    pub barnes_hut_threshold: usize,
+    // Precisión de Barnes–Hut: más bajo es más exacto y más lento
+    pub barnes_hut_theta: f32,
+    // Longitud de suavizado de la gravedad (px): por debajo de ella la fuerza
+    // deja de crecer y cae a cero en el centro
+    pub softening: f32,
+    // Método numérico con el que se mueven los asteroides
+    pub integrator: Integrator,
+    // Fracción de la velocidad de un impacto que pasa a los vértices del
+    // contorno del lado del golpe
+    pub dent_strength: f32,
+    // Coeficiente de rozamiento en los choques entre asteroides
+    pub friction: f32,
+    // Dos asteroides que se tocan se funden si su velocidad relativa es menor
+    // que esta fracción de la de escape; con 0 siempre rebotan
+    pub accretion: f32,
+}
+
+// Cálculo de la gravedad entre asteroides
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GravitySolver {
+    // Todas las parejas, O(n²)
+    Exact,
+    // Aproximación por árbol cuaternario, O(n log n)
+    BarnesHut,
+}
+
+// Integración del movimiento de los asteroides bajo su gravedad mutua. Los
+// métodos más precisos calculan la gravedad más veces por tick.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Integrator {
+    // Euler semi-implícito: primero la velocidad, luego la posición. Una
+    // evaluación de la gravedad por tick.
+    SemiImplicitEuler,
+    // Verlet en velocidad: segundo orden y simpléctico, dos evaluaciones
+    VelocityVerlet,
+    // Runge–Kutta clásico de cuarto orden, cuatro evaluaciones
+    Rk4,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlayerConfig {
+    pub thrust: f32,
+    pub rotation_speed: f32,
+    pub max_speed: f32,
+    // Factor que se aplica a la velocidad en cada tick
+    pub friction: f32,
+    pub lives: u32,
+    pub extra_life_score: u32,
+    pub invulnerability_time: f32,
+    pub respawn_safe_radius: f32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AsteroidConfig {
+    pub count: usize,
+    // Asteroides nuevos por cada nivel alcanzado
+    pub spawn_per_level: u32,
+    pub min_size: f32,
+    pub max_size: f32,
+    pub initial_speed: f32,
+    pub max_speed: f32,
+    pub max_acceleration: f32,
+    pub level_up_threshold: f32,
+    // Irregularidad del contorno: cada vértice está entre (1 - jaggedness) y
+    // (1 + jaggedness) veces el tamaño del centro
+    pub jaggedness: f32,
+    // Los asteroides mayores que esto se rompen en trozos al recibir un disparo
+    pub split_size: f32,
+    pub max_fragments: usize,
+    // Velocidad (px/s) con la que los trozos se alejan del punto de impacto
+    pub fragment_speed: f32,
+    // Dos asteroides no se funden si el resultado pasaría de este tamaño
+    pub max_merge_size: f32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BulletConfig {
+    pub speed: f32,
+    // Segundos que vive cada bala; su alcance es speed * lifetime
+    pub lifetime: f32,
+    // Balas en pantalla a la vez como máximo
+    pub max_bullets: usize,
+    // Segundos mínimos entre dos disparos
+    pub fire_cooldown: f32,
+    // Si las balas sienten la misma gravedad que la nave
+    pub gravity: bool,
+    // Segundos de trayectoria prevista que se dibujan delante de la nave
+    // cuando las balas sienten la gravedad; 0 la oculta
+    pub aim_preview: f32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ParticleConfig {
+    pub lifetime: f32,
+    pub speed: f32,
+    pub max_thruster_particles: usize,
+    pub thruster_lifetime: f32,
+}
+
+impl Default for PhysicsConfig {
+    fn default() -> Self {
+        PhysicsConfig {
+            gravity_constant: 6.67430e-11,
+            gravity_scale: 1e9,
+            player_gravity_factor: 10.0,
+            spring_constant: 0.9,
+            damping: 0.5,
+            max_deformation: 0.8,
+            gravity_solver: GravitySolver::BarnesHut,
+            barnes_hut_threshold: 200,
+            barnes_hut_theta: 0.5,
+            softening: 5.0,
+            integrator: Integrator::SemiImplicitEuler,
+            dent_strength: 0.3,
+            friction: 0.3,
+            accretion: 1.0,
+        }
+    }
+}
+
+impl Default for PlayerConfig {
+    fn default() -> Self {
+        PlayerConfig {
+            thrust: 300.0,
+            rotation_speed: 5.0,
+            max_speed: 200.0,
+            friction: 0.99,
+            lives: 3,
+            extra_life_score: 1000,
+            invulnerability_time: 3.0,
+            respawn_safe_radius: 150.0,
+        }
+    }
+}
+
+impl Default for AsteroidConfig {
+    fn default() -> Self {
+        AsteroidConfig {
+            count: 20,
+            spawn_per_level: 1,
+            min_size: 15.0,
+            max_size: 40.0,
+            initial_speed: 50.0,
+            max_speed: 200.0,
+            max_acceleration: 50.0,
+            level_up_threshold: 10.0,
+            jaggedness: 0.25,
+            split_size: 20.0,
+            max_fragments: 4,
+            fragment_speed: 40.0,
+            max_merge_size: 80.0,
+        }
+    }
+}
+
+impl Default for BulletConfig {
+    fn default() -> Self {
+        BulletConfig {
+            speed: 400.0,
+            lifetime: 1.5,
+            max_bullets: 8,
+            fire_cooldown: 0.15,
+            gravity: false,
+            aim_preview: 1.0,
+        }
+    }
+}
+
+impl Default for ParticleConfig {
+    fn default() -> Self {
+        ParticleConfig {
+            lifetime: 2.0,
+            speed: 80.0,
+            max_thruster_particles: 40,
+            thruster_lifetime: 0.7,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    Parse(toml::de::Error),
+    Invalid { field: &'static str, message: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, err) => write!(f, "no se pudo leer {}: {}", path.display(), err),
+            ConfigError::Parse(err) => write!(f, "configuración mal formada: {}", err),
+            ConfigError::Invalid { field, message } => write!(f, "valor no válido en `{}`: {}", field, message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+fn ensure(ok: bool, field: &'static str, message: impl FnOnce() -> String) -> Result<(), ConfigError> {
+    if ok {
+        Ok(())
+    } else {
+        Err(ConfigError::Invalid { field, message: message() })
+    }
+}
+
+fn positive(field: &'static str, value: f32) -> Result<(), ConfigError> {
+    ensure(value.is_finite() && value > 0.0, field, || format!("{} debe ser mayor que 0", value))
+}
+
+fn non_negative(field: &'static str, value: f32) -> Result<(), ConfigError> {
+    ensure(value.is_finite() && value >= 0.0, field, || format!("{} no puede ser negativo", value))
+}
+
+fn in_range(field: &'static str, value: f32, min: f32, max: f32) -> Result<(), ConfigError> {
+    ensure(value.is_finite() && (min..=max).contains(&value), field, || {
+        format!("{} debe estar entre {} y {}", value, min, max)
+    })
+}
+
+impl Config {
+    pub fn from_toml(text: &str) -> Result<Config, ConfigError> {
+        let config: Config = toml::from_str(text).map_err(ConfigError::Parse)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Config, ConfigError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|err| ConfigError::Io(path.to_path_buf(), err))?;
+        Config::from_toml(&text)
+    }
+
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("la configuración siempre se puede serializar")
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let physics = &self.physics;
+        non_negative("physics.gravity_constant", physics.gravity_constant)?;
+        non_negative("physics.gravity_scale", physics.gravity_scale)?;
+        non_negative("physics.player_gravity_factor", physics.player_gravity_factor)?;
+        non_negative("physics.spring_constant", physics.spring_constant)?;
+        non_negative("physics.damping", physics.damping)?;
+        in_range("physics.max_deformation", physics.max_deformation, 0.0, 0.95)?;
+        non_negative("physics.dent_strength", physics.dent_strength)?;
+        non_negative("physics.friction", physics.friction)?;
+        non_negative("physics.accretion", physics.accretion)?;
+        in_range("physics.barnes_hut_theta", physics.barnes_hut_theta, 0.0, 1.0)?;
+        positive("physics.softening", physics.softening)?;
+        ensure(physics.barnes_hut_threshold <= 10_000, "physics.barnes_hut_threshold", || {
+            format!("{} es demasiado (máximo 10000)", physics.barnes_hut_threshold)
+        })?;
+
+        let player = &self.player;
+        non_negative("player.thrust", player.thrust)?;
+        non_negative("player.rotation_speed", player.rotation_speed)?;
+        positive("player.max_speed", player.max_speed)?;
+        ensure(player.friction > 0.0 && player.friction <= 1.0, "player.friction", || {
+            format!("{} debe estar en (0, 1]", player.friction)
+        })?;
+        ensure(player.lives >= 1, "player.lives", || "hace falta al menos una vida".to_string())?;
+        ensure(player.extra_life_score >= 1, "player.extra_life_score", || {
+            "debe ser mayor que 0".to_string()
+        })?;
+        non_negative("player.invulnerability_time", player.invulnerability_time)?;
+        non_negative("player.respawn_safe_radius", player.respawn_safe_radius)?;
+
+        let asteroids = &self.asteroids;
+        ensure(asteroids.count <= 10_000, "asteroids.count", || {
+            format!("{} asteroides es demasiado (máximo 10000)", asteroids.count)
+        })?;
+        ensure(asteroids.spawn_per_level <= 100, "asteroids.spawn_per_level", || {
+            format!("{} asteroides por nivel es demasiado (máximo 100)", asteroids.spawn_per_level)
+        })?;
+        positive("asteroids.min_size", asteroids.min_size)?;
+        ensure(
+            asteroids.max_size.is_finite() && asteroids.max_size > asteroids.min_size,
+            "asteroids.max_size",
+            || format!("{} debe ser mayor que asteroids.min_size ({})", asteroids.max_size, asteroids.min_size),
+        )?;
+        positive("asteroids.initial_speed", asteroids.initial_speed)?;
+        positive("asteroids.max_speed", asteroids.max_speed)?;
+        positive("asteroids.max_acceleration", asteroids.max_acceleration)?;
+        positive("asteroids.level_up_threshold", asteroids.level_up_threshold)?;
+        in_range("asteroids.jaggedness", asteroids.jaggedness, 0.0, 0.9)?;
+        positive("asteroids.split_size", asteroids.split_size)?;
+        ensure((2..=16).contains(&asteroids.max_fragments), "asteroids.max_fragments", || {
+            format!("{} debe estar entre 2 y 16", asteroids.max_fragments)
+        })?;
+        non_negative("asteroids.fragment_speed", asteroids.fragment_speed)?;
+        positive("asteroids.max_merge_size", asteroids.max_merge_size)?;
+
+        let bullets = &self.bullets;
+        positive("bullets.speed", bullets.speed)?;
+        positive("bullets.lifetime", bullets.lifetime)?;
+        ensure(bullets.max_bullets >= 1, "bullets.max_bullets", || {
+            "hace falta al menos una bala".to_string()
+        })?;
+        non_negative("bullets.fire_cooldown", bullets.fire_cooldown)?;
+        non_negative("bullets.aim_preview", bullets.aim_preview)?;
+
+        let particles = &self.particles;
+        positive("particles.lifetime", particles.lifetime)?;
+        positive("particles.speed", particles.speed)?;
+        ensure(
+            particles.thruster_lifetime.is_finite() && particles.thruster_lifetime > 0.3,
+            "particles.thruster_lifetime",
+            || format!("{} debe ser mayor que 0.3", particles.thruster_lifetime),
+        )?;
+
+        Ok(())
+    }
+}
+
+const WATCH_INTERVAL: Duration = Duration::from_millis(250);
+
+// Vigila el fichero de configuración comprobando su fecha de modificación
+pub struct ConfigWatcher {
+    path: PathBuf,
+    stamp: Option<(SystemTime, u64)>,
+    last_check: Instant,
+}
+
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+impl ConfigWatcher {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        ConfigWatcher {
+            stamp: file_stamp(&path),
+            path,
+            last_check: Instant::now(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // Devuelve la configuración nueva (o el error al leerla) si el fichero ha
+    // cambiado desde la última comprobación
+    pub fn poll(&mut self) -> Option<Result<Config, ConfigError>> {
+        if self.last_check.elapsed() < WATCH_INTERVAL {
+            return None;
+        }
+        self.last_check = Instant::now();
+
+        let stamp = file_stamp(&self.path);
+        if stamp == self.stamp {
+            return None;
+        }
+        self.stamp = stamp;
+        Some(Config::load(&self.path))
+    }
+
+    // Como `poll`, pero aplica la configuración nueva sobre `config` solo si es
+    // válida; con un error se sigue usando la anterior
+    pub fn reload(&mut self, config: &mut Config) -> Option<Result<(), ConfigError>> {
+        let result = self.poll()?;
+        Some(result.map(|new| *config = new))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalid_field(config: &Config) -> &'static str {
+        match config.validate() {
+            Err(ConfigError::Invalid { field, .. }) => field,
+            other => panic!("se esperaba un valor no válido, no {:?}", other),
+        }
+    }
+
+    #[test]
+    fn oversized_counts_name_their_key() {
+        let mut config = Config::default();
+        config.asteroids.spawn_per_level = 1_000_000;
+        assert_eq!(invalid_field(&config), "asteroids.spawn_per_level");
+        assert!(config.validate().unwrap_err().to_string().contains("`asteroids.spawn_per_level`"));
+
+        let mut config = Config::default();
+        config.physics.barnes_hut_threshold = usize::MAX;
+        assert_eq!(invalid_field(&config), "physics.barnes_hut_threshold");
+        assert!(config.validate().unwrap_err().to_string().contains("`physics.barnes_hut_threshold`"));
+
+        assert!(Config::default().validate().is_ok());
+    }
+
+    #[test]
+    fn invalid_edit_keeps_the_last_valid_config() {
+        let path = std::env::temp_dir().join(format!("bubbleroid-watch-{}.toml", std::process::id()));
+        fs::write(&path, "[asteroids]\ncount = 5\n").unwrap();
+        let mut watcher = ConfigWatcher::new(&path);
+        let mut config = Config::load(&path).unwrap();
+        // Entre comprobaciones tiene que pasar el intervalo
+        let wait = || std::thread::sleep(WATCH_INTERVAL + Duration::from_millis(50));
+
+        wait();
+        assert!(watcher.reload(&mut config).is_none(), "sin cambios no hay nada que recargar");
+
+        fs::write(&path, "[asteroids]\ncount = 12\n").unwrap();
+        wait();
+        assert!(matches!(watcher.reload(&mut config), Some(Ok(()))));
+        assert_eq!(config.asteroids.count, 12);
+
+        fs::write(&path, "[asteroids]\ncount = 1000000\n").unwrap();
+        wait();
+        match watcher.reload(&mut config) {
+            Some(Err(ConfigError::Invalid { field, .. })) => assert_eq!(field, "asteroids.count"),
+            other => panic!("se esperaba un valor no válido, no {:?}", other),
+        }
+        assert_eq!(config.asteroids.count, 12, "se ha aplicado la configuración no válida");
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,14 @@
+pub const WINDOW_WIDTH: f32 = 1280.0;
+pub const WINDOW_HEIGHT: f32 = 720.0;
+pub const PLAYER_SIZE: f32 = 14.0;
+// Triángulo de la nave apuntando hacia -y, como se dibuja antes de girarla
+pub const SHIP_POINTS: [(f32, f32); 3] = [(0.0, -PLAYER_SIZE), (-PLAYER_SIZE / 2.0, PLAYER_SIZE), (PLAYER_SIZE / 2.0, PLAYER_SIZE)];
+pub const BULLET_SIZE: f32 = 3.0;
+pub const PARTICLE_SIZE: f32 = 2.0;
+pub const THRUSTER_PARTICLE_SIZE: f32 = 2.0;
+// Puntos de masa en el contorno de cada asteroide
+pub const ASTEROID_VERTICES: usize = 16;
+pub const RESPAWN_ATTEMPTS: usize = 50;
+pub const TICK_RATE: f32 = 120.0;
+pub const TICK_DT: f32 = 1.0 / TICK_RATE;
+pub const MAX_FRAME_TIME: f32 = 0.25;
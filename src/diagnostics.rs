@@ -0,0 +1,56 @@
+use std::fmt;
+
+use crate::config::PhysicsConfig;
+use crate::entities::Asteroid;
+use crate::physics::potential_energy;
+
+// Magnitudes que un integrador fiel conservaría entre choques. Como los
+// asteroides aceleran con la fuerza tal cual, su masa inercial es 1: la
+// energía cinética es ½·v² y el momento la suma de las velocidades. La nave no
+// cuenta, porque los asteroides no sienten su gravedad.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Diagnostics {
+    pub tick: u64,
+    pub kinetic: f64,
+    pub potential: f64,
+    pub momentum: (f64, f64),
+}
+
+impl Diagnostics {
+    pub fn measure(tick: u64, physics: &PhysicsConfig, asteroids: &[Asteroid]) -> Diagnostics {
+        let mut kinetic = 0.0;
+        let mut momentum = (0.0, 0.0);
+        for asteroid in asteroids {
+            let vel = (asteroid.vel.0 as f64, asteroid.vel.1 as f64);
+            kinetic += 0.5 * (vel.0 * vel.0 + vel.1 * vel.1);
+            momentum.0 += vel.0;
+            momentum.1 += vel.1;
+        }
+        Diagnostics {
+            tick,
+            kinetic,
+            potential: potential_energy(physics, asteroids),
+            momentum,
+        }
+    }
+
+    pub fn total_energy(&self) -> f64 {
+        self.kinetic + self.potential
+    }
+}
+
+// Una línea por tick, fácil de filtrar con grep o de cargar en una hoja de cálculo
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "tick={} cinetica={:.6e} potencial={:.6e} total={:.6e} momento=({:.6e}, {:.6e})",
+            self.tick,
+            self.kinetic,
+            self.potential,
+            self.total_energy(),
+            self.momentum.0,
+            self.momentum.1
+        )
+    }
+}
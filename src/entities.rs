@@ -0,0 +1,291 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::config::PhysicsConfig;
+use crate::constants::{ASTEROID_VERTICES, BULLET_SIZE};
+use crate::geometry::{gyration_squared, point_in_polygon, polygons_intersect, rotate, support};
+use crate::physics::wrapped_delta;
+
+// Color RGBA independiente del motor gráfico
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rgba(pub u8, pub u8, pub u8, pub u8);
+
+impl Rgba {
+    pub const RED: Rgba = Rgba(255, 0, 0, 255);
+    pub const GREEN: Rgba = Rgba(0, 255, 0, 255);
+    pub const BLUE: Rgba = Rgba(0, 0, 255, 255);
+    pub const WHITE: Rgba = Rgba(255, 255, 255, 255);
+    pub const YELLOW: Rgba = Rgba(255, 255, 0, 255);
+    pub const ORANGE: Rgba = Rgba(255, 165, 0, 255);
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Bullet {
+    pub pos: (f32, f32),
+    pub prev_pos: (f32, f32),
+    pub vel: (f32, f32),
+    // Segundos que le quedan antes de desaparecer
+    pub life: f32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ThrusterParticle {
+    pub pos: (f32, f32),
+    pub vel: (f32, f32),
+    pub color: Rgba,
+    pub life: f32,
+    pub initial_life: f32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Asteroid {
+    pub pos: (f32, f32),
+    pub prev_pos: (f32, f32),
+    pub vel: (f32, f32),
+    pub size: f32,
+    pub is_destroyed: bool,
+    pub mass: f32,
+    // Giro del cuerpo (rad) y su velocidad angular (rad/s)
+    pub angle: f32,
+    pub angular_vel: f32,
+    // Momento de inercia respecto al centro, con la masa repartida por igual
+    // sobre la forma de reposo
+    pub inertia: f32,
+    // Contorno blando, en coordenadas del cuerpo sin girar
+    pub vertices: Vec<Vertex>,
+}
+
+// Punto de masa del contorno de un asteroide
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Vertex {
+    // Posición de reposo, que da la forma irregular del asteroide
+    pub rest: (f32, f32),
+    pub offset: (f32, f32),
+    pub vel: (f32, f32),
+}
+
+impl Vertex {
+    fn at_rest(rest: (f32, f32)) -> Vertex {
+        Vertex {
+            rest,
+            offset: rest,
+            vel: (0.0, 0.0),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Particle {
+    pub pos: (f32, f32),
+    pub vel: (f32, f32),
+    pub life: f32,
+    pub color: Rgba,
+}
+
+impl ThrusterParticle {
+    pub fn new(pos: (f32, f32), vel: (f32, f32), color: Rgba, lifetime: f32) -> Self {
+        ThrusterParticle {
+            pos,
+            vel,
+            color,
+            life: lifetime,
+            initial_life: lifetime,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.pos.0 += self.vel.0 * dt;
+        self.pos.1 += self.vel.1 * dt;
+        self.life -= dt;
+    }
+}
+
+impl Bullet {
+    pub fn new(pos: (f32, f32), vel: (f32, f32), lifetime: f32) -> Self {
+        Bullet {
+            pos,
+            prev_pos: pos,
+            vel,
+            life: lifetime,
+        }
+    }
+
+    // Con la misma densidad que los asteroides, una bala es muy ligera
+    pub fn mass(&self) -> f32 {
+        mass_of(BULLET_SIZE)
+    }
+}
+
+impl Asteroid {
+    // Asteroide con forma de polígono regular
+    pub fn new(pos: (f32, f32), vel: (f32, f32), size: f32) -> Self {
+        let shape = (0..ASTEROID_VERTICES).map(|i| ring_point(i, size, 0.0)).collect();
+        Asteroid::with_shape(pos, vel, size, shape)
+    }
+
+    // Asteroide con un contorno irregular: cada vértice a una distancia del
+    // centro entre (1 - jaggedness) y (1 + jaggedness) veces el tamaño
+    pub fn jagged(pos: (f32, f32), vel: (f32, f32), size: f32, jaggedness: f32, rng: &mut impl Rng) -> Self {
+        let shape = (0..ASTEROID_VERTICES)
+            .map(|i| {
+                let radius = size * (1.0 + rng.gen_range(-1.0..=1.0) * jaggedness);
+                ring_point(i, radius, rng.gen_range(-0.3..=0.3))
+            })
+            .collect();
+        Asteroid::with_shape(pos, vel, size, shape)
+    }
+
+    fn with_shape(pos: (f32, f32), vel: (f32, f32), size: f32, shape: Vec<(f32, f32)>) -> Self {
+        let mass = mass_of(size);
+        Asteroid {
+            pos,
+            prev_pos: pos,
+            vel,
+            size,
+            is_destroyed: false,
+            mass,
+            angle: 0.0,
+            angular_vel: 0.0,
+            inertia: mass * gyration_squared(&shape),
+            vertices: shape.into_iter().map(Vertex::at_rest).collect(),
+        }
+    }
+
+    // Muelles del contorno: cada vértice tira hacia su posición de reposo y
+    // hacia la distancia de reposo con sus dos vecinos, con amortiguación.
+    // Los vértices tienen masa 1.
+    pub fn update_deformation(&mut self, dt: f32, physics: &PhysicsConfig) {
+        let count = self.vertices.len();
+        let forces: Vec<(f32, f32)> = (0..count)
+            .map(|i| {
+                let vertex = &self.vertices[i];
+                let mut force = (
+                    -physics.spring_constant * (vertex.offset.0 - vertex.rest.0) - physics.damping * vertex.vel.0,
+                    -physics.spring_constant * (vertex.offset.1 - vertex.rest.1) - physics.damping * vertex.vel.1,
+                );
+                for j in [(i + count - 1) % count, (i + 1) % count] {
+                    let other = &self.vertices[j];
+                    let (dx, dy) = (other.offset.0 - vertex.offset.0, other.offset.1 - vertex.offset.1);
+                    let length = (dx * dx + dy * dy).sqrt();
+                    if length > 0.0 {
+                        let rest_length = distance(vertex.rest, other.rest);
+                        let stretch = physics.spring_constant * (length - rest_length) / length;
+                        force.0 += stretch * dx;
+                        force.1 += stretch * dy;
+                    }
+                }
+                force
+            })
+            .collect();
+
+        for (vertex, force) in self.vertices.iter_mut().zip(forces) {
+            vertex.vel.0 += force.0 * dt;
+            vertex.vel.1 += force.1 * dt;
+            vertex.offset.0 += vertex.vel.0 * dt;
+            vertex.offset.1 += vertex.vel.1 * dt;
+
+            // Limitar la deformación de cada vértice a una fracción de su
+            // distancia de reposo, para que nunca cruce el centro
+            let limit = physics.max_deformation * distance(vertex.rest, (0.0, 0.0));
+            let displacement = distance(vertex.offset, vertex.rest);
+            if displacement > limit {
+                let scale = limit / displacement;
+                vertex.offset.0 = vertex.rest.0 + (vertex.offset.0 - vertex.rest.0) * scale;
+                vertex.offset.1 = vertex.rest.1 + (vertex.offset.1 - vertex.rest.1) * scale;
+            }
+        }
+    }
+
+    // Abolladura por un golpe en la dirección `direction` (vector unitario del
+    // centro al punto de contacto): los vértices de ese lado reciben una
+    // velocidad hacia dentro que decae rápido con el ángulo
+    pub fn dent(&mut self, direction: (f32, f32), speed: f32) {
+        // Los vértices no están girados: la dirección se pasa al cuerpo
+        let direction = rotate(direction, -self.angle);
+        for vertex in &mut self.vertices {
+            let radius = distance(vertex.rest, (0.0, 0.0));
+            if radius == 0.0 {
+                continue;
+            }
+            let radial = (vertex.rest.0 / radius, vertex.rest.1 / radius);
+            let facing = (radial.0 * direction.0 + radial.1 * direction.1).max(0.0);
+            let strength = speed * facing.powi(8);
+            vertex.vel.0 -= radial.0 * strength;
+            vertex.vel.1 -= radial.1 * strength;
+        }
+    }
+
+    // Oscilación de todo el contorno en forma de óvalo, como una burbuja al
+    // recibir un golpe fuerte: `speed` es la velocidad inicial de los vértices
+    pub fn wobble(&mut self, speed: f32) {
+        for vertex in &mut self.vertices {
+            let radius = distance(vertex.rest, (0.0, 0.0));
+            if radius == 0.0 {
+                continue;
+            }
+            let angle = vertex.rest.1.atan2(vertex.rest.0);
+            let strength = speed * (2.0 * angle).cos();
+            vertex.vel.0 += vertex.rest.0 / radius * strength;
+            vertex.vel.1 += vertex.rest.1 / radius * strength;
+        }
+    }
+
+    // Radio del círculo que contiene todo el contorno deformado
+    pub fn bounding_radius(&self) -> f32 {
+        self.vertices.iter().map(|vertex| distance(vertex.offset, (0.0, 0.0))).fold(0.0, f32::max)
+    }
+
+    // Contorno deformado y girado, relativo al centro
+    pub fn local_outline(&self) -> Vec<(f32, f32)> {
+        self.vertices.iter().map(|vertex| rotate(vertex.offset, self.angle)).collect()
+    }
+
+    // Si el punto del mundo está dentro del contorno deformado, también al
+    // otro lado de un borde
+    pub fn contains(&self, point: (f32, f32)) -> bool {
+        let delta = wrapped_delta(self.pos, point);
+        delta.0 * delta.0 + delta.1 * delta.1 <= self.bounding_radius().powi(2)
+            && point_in_polygon(delta, &self.local_outline())
+    }
+
+    // Si el contorno deformado toca un polígono dado por su centro en el
+    // mundo y sus vértices relativos a ese centro
+    pub fn overlaps_polygon(&self, center: (f32, f32), polygon: &[(f32, f32)]) -> bool {
+        let (dx, dy) = wrapped_delta(self.pos, center);
+        let moved: Vec<(f32, f32)> = polygon.iter().map(|p| (p.0 + dx, p.1 + dy)).collect();
+        polygons_intersect(&self.local_outline(), &moved)
+    }
+
+    pub fn overlaps(&self, other: &Asteroid) -> bool {
+        let (dx, dy) = wrapped_delta(self.pos, other.pos);
+        let reach = self.bounding_radius() + other.bounding_radius();
+        dx * dx + dy * dy < reach * reach && self.overlaps_polygon(other.pos, &other.local_outline())
+    }
+
+    // Lo más lejos que llega el contorno deformado desde el centro en la
+    // dirección unitaria dada
+    pub fn extent(&self, direction: (f32, f32)) -> f32 {
+        support(&self.local_outline(), direction)
+    }
+
+    // Contorno deformado en coordenadas del mundo, usado por el renderizado
+    pub fn outline(&self) -> Vec<(f32, f32)> {
+        self.local_outline().into_iter().map(|(x, y)| (self.pos.0 + x, self.pos.1 + y)).collect()
+    }
+}
+
+// La masa va con el área: un asteroide de tamaño `size` pesa como un disco de ese radio
+fn mass_of(size: f32) -> f32 {
+    size * size * std::f32::consts::PI * 0.1
+}
+
+// Punto `i` del anillo a distancia `radius` del centro, con el ángulo
+// desplazado `jitter` veces la separación entre vértices
+fn ring_point(i: usize, radius: f32, jitter: f32) -> (f32, f32) {
+    let angle = (i as f32 + jitter) * std::f32::consts::TAU / ASTEROID_VERTICES as f32;
+    (radius * angle.cos(), radius * angle.sin())
+}
+
+fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
@@ -0,0 +1,115 @@
+use rand::Rng;
+
+use crate::config::Config;
+use crate::entities::{Asteroid, Bullet};
+use crate::physics::{wrap_position, wrapped_delta};
+
+// Trozos en que se rompe `asteroid` al recibir `bullet`; ninguno si es
+// demasiado pequeño. Los trozos suman la masa del asteroide y, con la bala
+// incluida, el mismo momento lineal: la bala desaparece pero su impulso pasa
+// a los trozos. Salen repartidos en un anillo sin tocarse, con el mismo centro
+// de masas, y se alejan del punto de impacto.
+pub fn fragment(asteroid: &Asteroid, bullet: &Bullet, config: &Config, rng: &mut impl Rng) -> Vec<Asteroid> {
+    let asteroids = &config.asteroids;
+    if asteroid.size <= asteroids.split_size {
+        return Vec::new();
+    }
+
+    // Más trozos cuanto más grande. La masa va con el cuadrado del tamaño, así
+    // que repartir el cuadrado según unos pesos conserva la masa.
+    let count = ((2.0 * asteroid.size / asteroids.split_size) as usize).clamp(2, asteroids.max_fragments);
+    let weights: Vec<f32> = (0..count).map(|_| rng.gen_range(0.6..1.4)).collect();
+    let total_weight: f32 = weights.iter().sum();
+    let mut fragments: Vec<Asteroid> = weights
+        .iter()
+        .map(|weight| {
+            let size = asteroid.size * (weight / total_weight).sqrt();
+            Asteroid::jagged(asteroid.pos, asteroid.vel, size, asteroids.jaggedness, rng)
+        })
+        .collect();
+    let total_mass: f32 = fragments.iter().map(|fragment| fragment.mass).sum();
+
+    // Radio del anillo para que ningún par de trozos se solape, contando
+    // también los que no son vecinos
+    let radii: Vec<f32> = fragments.iter().map(Asteroid::bounding_radius).collect();
+    let step = std::f32::consts::TAU / count as f32;
+    let mut ring = 0.0_f32;
+    for i in 0..count {
+        for j in i + 1..count {
+            let chord = 2.0 * (std::f32::consts::PI * (j - i) as f32 / count as f32).sin();
+            ring = ring.max((radii[i] + radii[j]) / chord);
+        }
+    }
+    // Un poco de holgura para que el redondeo no los deje rozándose
+    ring *= 1.01;
+
+    let start = rng.gen_range(0.0..std::f32::consts::TAU);
+    let mut offsets: Vec<(f32, f32)> = (0..count)
+        .map(|i| {
+            let angle = start + step * i as f32;
+            (ring * angle.cos(), ring * angle.sin())
+        })
+        .collect();
+    let center = mass_weighted_mean(&fragments, &offsets, total_mass);
+    for offset in &mut offsets {
+        offset.0 -= center.0;
+        offset.1 -= center.1;
+    }
+
+    // Punto de impacto en el borde, del lado por el que entró la bala
+    let (dx, dy) = wrapped_delta(asteroid.pos, bullet.pos);
+    let dist = (dx * dx + dy * dy).sqrt();
+    let contact = if dist > 0.0 { (dx / dist, dy / dist) } else { (1.0, 0.0) };
+    let impact = (contact.0 * asteroid.size, contact.1 * asteroid.size);
+
+    // Cada trozo se aleja del impacto y conserva la velocidad que le daba el
+    // giro del asteroide en su sitio
+    let mut kicks: Vec<(f32, f32)> = offsets
+        .iter()
+        .map(|offset| {
+            let away = (offset.0 - impact.0, offset.1 - impact.1);
+            let length = (away.0 * away.0 + away.1 * away.1).sqrt().max(f32::EPSILON);
+            let speed = asteroids.fragment_speed / length;
+            (
+                away.0 * speed - asteroid.angular_vel * offset.1,
+                away.1 * speed + asteroid.angular_vel * offset.0,
+            )
+        })
+        .collect();
+    // Las patadas no pueden mover el centro de masas
+    let drift = mass_weighted_mean(&fragments, &kicks, total_mass);
+    for kick in &mut kicks {
+        kick.0 -= drift.0;
+        kick.1 -= drift.1;
+    }
+
+    // Velocidad del centro de masas con el impulso de la bala
+    let bullet_mass = bullet.mass();
+    let base = (
+        asteroid.vel.0 + bullet_mass * bullet.vel.0 / total_mass,
+        asteroid.vel.1 + bullet_mass * bullet.vel.1 / total_mass,
+    );
+    let relative_speed = ((bullet.vel.0 - asteroid.vel.0).powi(2) + (bullet.vel.1 - asteroid.vel.1).powi(2)).sqrt();
+
+    for ((fragment, offset), kick) in fragments.iter_mut().zip(&offsets).zip(&kicks) {
+        fragment.pos = wrap_position((asteroid.pos.0 + offset.0, asteroid.pos.1 + offset.1));
+        fragment.prev_pos = fragment.pos;
+        fragment.vel = (base.0 + kick.0, base.1 + kick.1);
+        // Los trozos siguen girando como el asteroide entero
+        fragment.angle = asteroid.angle;
+        fragment.angular_vel = asteroid.angular_vel;
+        // Abollados por el lado donde entró la bala
+        fragment.wobble(8.0);
+        let dent_speed = config.physics.dent_strength * relative_speed * bullet_mass / fragment.mass;
+        fragment.dent(contact, dent_speed);
+    }
+
+    fragments
+}
+
+fn mass_weighted_mean(fragments: &[Asteroid], values: &[(f32, f32)], total_mass: f32) -> (f32, f32) {
+    let sum = fragments.iter().zip(values).fold((0.0, 0.0), |sum, (fragment, value)| {
+        (sum.0 + fragment.mass * value.0, sum.1 + fragment.mass * value.1)
+    });
+    (sum.0 / total_mass, sum.1 / total_mass)
+}
@@ -0,0 +1,83 @@
+// Pruebas de intersección entre polígonos cualesquiera (no hace falta que
+// sean convexos: un asteroide abollado no lo es). Los polígonos son listas de
+// vértices en orden, con el último unido al primero.
+
+// Regla par-impar: un rayo horizontal desde el punto cruza el borde un número
+// impar de veces si el punto está dentro
+pub fn point_in_polygon(point: (f32, f32), polygon: &[(f32, f32)]) -> bool {
+    let mut inside = false;
+    for (a, b) in edges(polygon) {
+        if (a.1 > point.1) != (b.1 > point.1) {
+            let x = a.0 + (point.1 - a.1) * (b.0 - a.0) / (b.1 - a.1);
+            if point.0 < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+// Dos polígonos se tocan si se cortan dos de sus lados o si uno está entero
+// dentro del otro
+pub fn polygons_intersect(a: &[(f32, f32)], b: &[(f32, f32)]) -> bool {
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+    edges(a).any(|(p1, p2)| edges(b).any(|(q1, q2)| segments_intersect(p1, p2, q1, q2)))
+        || point_in_polygon(a[0], b)
+        || point_in_polygon(b[0], a)
+}
+
+// Función soporte: lo más lejos que llega el polígono en la dirección dada
+pub fn support(polygon: &[(f32, f32)], direction: (f32, f32)) -> f32 {
+    polygon
+        .iter()
+        .map(|p| p.0 * direction.0 + p.1 * direction.1)
+        .fold(f32::NEG_INFINITY, f32::max)
+}
+
+// Media del cuadrado de la distancia al origen sobre la superficie del
+// polígono: multiplicada por la masa da el momento de inercia respecto al origen
+pub fn gyration_squared(polygon: &[(f32, f32)]) -> f32 {
+    let mut area = 0.0;
+    let mut moment = 0.0;
+    for (a, b) in edges(polygon) {
+        let cross = a.0 * b.1 - a.1 * b.0;
+        area += cross / 2.0;
+        moment += cross * (a.0 * a.0 + a.1 * a.1 + a.0 * b.0 + a.1 * b.1 + b.0 * b.0 + b.1 * b.1) / 12.0;
+    }
+    if area == 0.0 {
+        return 0.0;
+    }
+    moment / area
+}
+
+// Gira un vector `angle` radianes
+pub fn rotate(v: (f32, f32), angle: f32) -> (f32, f32) {
+    let (sin, cos) = angle.sin_cos();
+    (v.0 * cos - v.1 * sin, v.0 * sin + v.1 * cos)
+}
+
+fn edges(polygon: &[(f32, f32)]) -> impl Iterator<Item = ((f32, f32), (f32, f32))> + '_ {
+    polygon.iter().zip(polygon.iter().cycle().skip(1)).map(|(&a, &b)| (a, b))
+}
+
+fn cross(o: (f32, f32), a: (f32, f32), b: (f32, f32)) -> f32 {
+    (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+}
+
+// Los segmentos se cortan si los extremos de cada uno quedan a lados
+// distintos del otro; tocarse en un extremo también cuenta
+fn segments_intersect(p1: (f32, f32), p2: (f32, f32), q1: (f32, f32), q2: (f32, f32)) -> bool {
+    let d1 = cross(q1, q2, p1);
+    let d2 = cross(q1, q2, p2);
+    let d3 = cross(p1, p2, q1);
+    let d4 = cross(p1, p2, q2);
+    d1 * d2 <= 0.0 && d3 * d4 <= 0.0 && !(d1 == 0.0 && d2 == 0.0 && !collinear_overlap(p1, p2, q1, q2))
+}
+
+// Segmentos sobre la misma recta: se cortan si sus proyecciones se solapan
+fn collinear_overlap(p1: (f32, f32), p2: (f32, f32), q1: (f32, f32), q2: (f32, f32)) -> bool {
+    let overlaps = |a: f32, b: f32, c: f32, d: f32| a.min(b) <= c.max(d) && c.min(d) <= a.max(b);
+    overlaps(p1.0, p2.0, q1.0, q2.0) && overlaps(p1.1, p2.1, q1.1, q2.1)
+}
@@ -0,0 +1,107 @@
+use crate::constants::{WINDOW_HEIGHT, WINDOW_WIDTH};
+
+const MIN_CELL_SIZE: f32 = 16.0;
+
+// Rejilla uniforme sobre el mundo toroidal para la fase amplia de colisiones.
+// Cada objeto se guarda en la celda de su centro; una consulta recorre las
+// celdas que toca el círculo pedido, dando la vuelta por los bordes, y devuelve
+// candidatos que luego hay que comprobar con la distancia exacta.
+//
+// Las celdas se guardan contiguas (índice de inicio por celda más una lista de
+// objetos) para no reservar memoria en cada tick.
+#[derive(Clone, Debug, Default)]
+pub struct SpatialGrid {
+    cols: usize,
+    rows: usize,
+    cell_width: f32,
+    cell_height: f32,
+    cell_start: Vec<usize>,
+    entries: Vec<usize>,
+    cells: Vec<usize>,
+}
+
+impl SpatialGrid {
+    // Reparte los objetos en celdas de al menos `cell_size` de lado. El número
+    // de celdas se redondea para que cubran el mundo exactamente.
+    pub fn build(&mut self, cell_size: f32, positions: impl Iterator<Item = (f32, f32)>) {
+        // Sin objetos el tamaño pedido es 0: celdas de un píxel serían casi un
+        // millón que vaciar en cada tick
+        let cell_size = cell_size.max(MIN_CELL_SIZE);
+        self.cols = ((WINDOW_WIDTH / cell_size) as usize).max(1);
+        self.rows = ((WINDOW_HEIGHT / cell_size) as usize).max(1);
+        self.cell_width = WINDOW_WIDTH / self.cols as f32;
+        self.cell_height = WINDOW_HEIGHT / self.rows as f32;
+
+        self.cells.clear();
+        for pos in positions {
+            let cell = self.cell_index(self.col(pos.0), self.row(pos.1));
+            self.cells.push(cell);
+        }
+
+        // Ordenación por recuento: primero cuántos hay en cada celda, luego dónde empieza cada una
+        self.cell_start.clear();
+        self.cell_start.resize(self.cols * self.rows + 1, 0);
+        for &cell in &self.cells {
+            self.cell_start[cell + 1] += 1;
+        }
+        for i in 1..self.cell_start.len() {
+            self.cell_start[i] += self.cell_start[i - 1];
+        }
+        self.entries.clear();
+        self.entries.resize(self.cells.len(), 0);
+        let mut next = self.cell_start.clone();
+        for (index, &cell) in self.cells.iter().enumerate() {
+            self.entries[next[cell]] = index;
+            next[cell] += 1;
+        }
+    }
+
+    fn col(&self, x: f32) -> isize {
+        (x / self.cell_width).floor() as isize
+    }
+
+    fn row(&self, y: f32) -> isize {
+        (y / self.cell_height).floor() as isize
+    }
+
+    fn cell_index(&self, col: isize, row: isize) -> usize {
+        let col = col.rem_euclid(self.cols as isize) as usize;
+        let row = row.rem_euclid(self.rows as isize) as usize;
+        row * self.cols + col
+    }
+
+    // Celdas de `lo` a `hi` dando la vuelta; si el rango cubre todo el eje, cada una una sola vez
+    fn span(lo: isize, hi: isize, count: usize) -> std::ops::RangeInclusive<isize> {
+        if hi - lo + 1 >= count as isize {
+            0..=count as isize - 1
+        } else {
+            lo..=hi
+        }
+    }
+
+    // Llama a `visit` con cada objeto cuya celda toca el círculo de centro
+    // `pos` y radio `radius`. El radio tiene que incluir el de los objetos.
+    pub fn query(&self, pos: (f32, f32), radius: f32, mut visit: impl FnMut(usize)) {
+        if self.cells.is_empty() {
+            return;
+        }
+        let cols = Self::span(self.col(pos.0 - radius), self.col(pos.0 + radius), self.cols);
+        let rows = Self::span(self.row(pos.1 - radius), self.row(pos.1 + radius), self.rows);
+        for row in rows {
+            for col in cols.clone() {
+                let cell = self.cell_index(col, row);
+                for &index in &self.entries[self.cell_start[cell]..self.cell_start[cell + 1]] {
+                    visit(index);
+                }
+            }
+        }
+    }
+
+    // Candidatos de la consulta en orden creciente, para recorrerlos en el
+    // mismo orden que una búsqueda exhaustiva
+    pub fn query_sorted(&self, pos: (f32, f32), radius: f32, out: &mut Vec<usize>) {
+        out.clear();
+        self.query(pos, radius, |index| out.push(index));
+        out.sort_unstable();
+    }
+}
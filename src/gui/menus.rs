@@ -0,0 +1,421 @@
+use ggez::event::KeyCode;
+use ggez::graphics::Color;
+use ggez::{Context, GameResult};
+
+use bubbleroid::{HighScore, HighScores, SaveError, SaveGame, World, NAME_LENGTH};
+
+use super::playing::Playing;
+use super::render::{draw_centered_text, draw_dim, draw_text};
+use super::{Scene, Shared, Transition};
+
+// Columnas de la tabla de puntuaciones: posición x y cabecera
+const HIGH_SCORE_COLUMNS: [(f32, &str); 7] = [
+    (230.0, "#"),
+    (280.0, "Nombre"),
+    (390.0, "Puntos"),
+    (500.0, "Nivel"),
+    (590.0, "Hits"),
+    (680.0, "Fecha"),
+    (830.0, "Semilla"),
+];
+
+// Mueve la selección de un menú de `len` opciones, dando la vuelta en los extremos
+fn move_selection(selected: usize, len: usize, keycode: KeyCode) -> usize {
+    match keycode {
+        KeyCode::Up => (selected + len - 1) % len,
+        KeyCode::Down => (selected + 1) % len,
+        _ => selected,
+    }
+}
+
+fn draw_menu(ctx: &mut Context, options: &[String], selected: usize, y: f32) -> GameResult<()> {
+    for (i, option) in options.iter().enumerate() {
+        let (text, color) = if i == selected {
+            (format!("> {} <", option), Color::YELLOW)
+        } else {
+            (option.clone(), Color::GREEN)
+        };
+        draw_centered_text(ctx, &text, 28.0, y + i as f32 * 40.0, color)?;
+    }
+    Ok(())
+}
+
+// Tabla de mejores puntuaciones, con la fila `highlight` resaltada
+fn draw_high_scores(ctx: &mut Context, high_scores: &HighScores, highlight: Option<usize>, y: f32) -> GameResult<()> {
+    if high_scores.entries().is_empty() {
+        return draw_centered_text(ctx, "Aún no hay puntuaciones", 20.0, y, Color::WHITE);
+    }
+
+    for (x, header) in HIGH_SCORE_COLUMNS {
+        draw_text(ctx, header, 20.0, x, y, Color::GREEN)?;
+    }
+    for (i, entry) in high_scores.entries().iter().enumerate() {
+        let color = if highlight == Some(i) { Color::YELLOW } else { Color::WHITE };
+        let row_y = y + 28.0 + i as f32 * 24.0;
+        let cells = [
+            (i + 1).to_string(),
+            entry.name.clone(),
+            entry.score.to_string(),
+            entry.level.to_string(),
+            entry.hits.to_string(),
+            entry.date.clone(),
+            entry.seed.to_string(),
+        ];
+        for ((x, _), cell) in HIGH_SCORE_COLUMNS.iter().zip(cells) {
+            draw_text(ctx, &cell, 20.0, *x, row_y, color)?;
+        }
+    }
+    Ok(())
+}
+
+// Resultado de una partida terminada
+#[derive(Clone)]
+pub struct RunSummary {
+    pub score: u32,
+    pub level: u32,
+    pub hits: f32,
+    pub seed: u64,
+}
+
+impl RunSummary {
+    fn new(world: &World) -> RunSummary {
+        RunSummary {
+            score: world.score,
+            level: world.level,
+            hits: world.destroyed_count,
+            seed: world.seed,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum TitleOption {
+    Continue,
+    Play,
+    Settings,
+    Quit,
+}
+
+impl TitleOption {
+    fn label(self) -> &'static str {
+        match self {
+            TitleOption::Continue => "Continuar partida",
+            TitleOption::Play => "Jugar",
+            TitleOption::Settings => "Ajustes",
+            TitleOption::Quit => "Salir",
+        }
+    }
+}
+
+// "Continuar partida" solo aparece si hay una partida guardada
+fn title_options(shared: &Shared) -> Vec<TitleOption> {
+    let mut options = vec![TitleOption::Play, TitleOption::Settings, TitleOption::Quit];
+    if shared.has_save {
+        options.insert(0, TitleOption::Continue);
+    }
+    options
+}
+
+// Carga la partida guardada y borra el fichero: una partida se continúa una sola vez
+fn continue_game(shared: &mut Shared) -> Transition {
+    match SaveGame::load(&shared.save_path) {
+        Ok(save) => {
+            shared.has_save = false;
+            if let Err(err) = std::fs::remove_file(&shared.save_path) {
+                eprintln!("no se pudo borrar {}: {}", shared.save_path.display(), err);
+            }
+            Transition::Replace(Box::new(Playing::from_save(save, shared)))
+        }
+        Err(err) => {
+            // Un error de lectura puede ser pasajero: la partida sigue ahí para
+            // intentarlo otra vez. Si falta el fichero o su contenido no vale, ya
+            // no se podrá cargar.
+            if !matches!(&err, SaveError::Io(io_err) if io_err.kind() != std::io::ErrorKind::NotFound) {
+                shared.has_save = false;
+            }
+            shared.show_message(format!("no se pudo continuar la partida: {}", err), true);
+            Transition::None
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Title {
+    selected: usize,
+}
+
+impl Scene for Title {
+    fn draw(&mut self, ctx: &mut Context, shared: &Shared) -> GameResult<()> {
+        draw_centered_text(ctx, "BUBBLEROID", 72.0, 60.0, Color::GREEN)?;
+        let options: Vec<String> = title_options(shared).iter().map(|option| option.label().to_string()).collect();
+        draw_menu(ctx, &options, self.selected.min(options.len() - 1), 170.0)?;
+        draw_high_scores(ctx, &shared.high_scores, None, 350.0)?;
+        if let Some(seed) = shared.seed {
+            draw_centered_text(ctx, &format!("Semilla fija: {}", seed), 20.0, 628.0, Color::WHITE)?;
+        }
+        draw_centered_text(
+            ctx,
+            "Flechas: girar y propulsar   Espacio: disparar   Esc/P: pausa",
+            20.0,
+            660.0,
+            Color::WHITE,
+        )
+    }
+
+    fn key_down(&mut self, keycode: KeyCode, shared: &mut Shared) -> Transition {
+        let options = title_options(shared);
+        self.selected = self.selected.min(options.len() - 1);
+        match keycode {
+            KeyCode::Return | KeyCode::Space => match options[self.selected] {
+                TitleOption::Continue => continue_game(shared),
+                TitleOption::Play => Transition::Replace(Box::new(Playing::new(shared))),
+                TitleOption::Settings => Transition::Push(Box::new(Settings::default())),
+                TitleOption::Quit => Transition::Quit,
+            },
+            KeyCode::Escape => Transition::Quit,
+            _ => {
+                self.selected = move_selection(self.selected, options.len(), keycode);
+                Transition::None
+            }
+        }
+    }
+}
+
+// Ajustes rápidos para las partidas siguientes. Cambian la configuración en
+// memoria; para guardarlos hay que editar el fichero de configuración.
+#[derive(Default)]
+pub struct Settings {
+    selected: usize,
+}
+
+const SETTINGS_COUNT: usize = 4;
+const GRAVITY_STEP: f32 = 0.25;
+
+impl Settings {
+    fn adjust(&self, shared: &mut Shared, delta: i32) {
+        let mut config = shared.config.clone();
+        match self.selected {
+            0 => config.player.lives = (config.player.lives as i32 + delta).clamp(1, 9) as u32,
+            1 => config.asteroids.count = (config.asteroids.count as i32 + delta * 5).clamp(0, 100) as usize,
+            3 => config.bullets.gravity = !config.bullets.gravity,
+            _ => {
+                // La gravedad se ajusta en pasos relativos al valor por defecto
+                let default_scale = bubbleroid::PhysicsConfig::default().gravity_scale;
+                let factor = (config.physics.gravity_scale / default_scale + delta as f32 * GRAVITY_STEP).clamp(0.0, 4.0);
+                config.physics.gravity_scale = factor * default_scale;
+            }
+        }
+        if config.validate().is_ok() && config != shared.config {
+            shared.set_config(config);
+        }
+    }
+}
+
+impl Scene for Settings {
+    fn draw(&mut self, ctx: &mut Context, shared: &Shared) -> GameResult<()> {
+        let config = &shared.config;
+        let default_scale = bubbleroid::PhysicsConfig::default().gravity_scale;
+        let options = [
+            format!("Vidas: {}", config.player.lives),
+            format!("Asteroides iniciales: {}", config.asteroids.count),
+            format!("Gravedad: {:.0}%", config.physics.gravity_scale / default_scale * 100.0),
+            format!("Balas con gravedad: {}", if config.bullets.gravity { "Sí" } else { "No" }),
+        ];
+        draw_centered_text(ctx, "AJUSTES", 48.0, 160.0, Color::GREEN)?;
+        draw_menu(ctx, &options, self.selected, 300.0)?;
+        draw_centered_text(ctx, "Izquierda/Derecha: cambiar   Esc: volver", 20.0, 640.0, Color::WHITE)
+    }
+
+    fn key_down(&mut self, keycode: KeyCode, shared: &mut Shared) -> Transition {
+        match keycode {
+            KeyCode::Escape | KeyCode::Return => return Transition::Pop,
+            KeyCode::Left => self.adjust(shared, -1),
+            KeyCode::Right => self.adjust(shared, 1),
+            _ => self.selected = move_selection(self.selected, SETTINGS_COUNT, keycode),
+        }
+        Transition::None
+    }
+}
+
+#[derive(Clone, Copy)]
+enum PauseOption {
+    Resume,
+    Restart,
+    SaveAndQuit,
+    Quit,
+}
+
+impl PauseOption {
+    fn label(self) -> &'static str {
+        match self {
+            PauseOption::Resume => "Continuar",
+            PauseOption::Restart => "Reiniciar",
+            PauseOption::SaveAndQuit => "Guardar y salir",
+            PauseOption::Quit => "Salir",
+        }
+    }
+}
+
+// Encima de la partida, que queda congelada mientras esté abierta
+pub struct Paused {
+    options: Vec<PauseOption>,
+    selected: usize,
+}
+
+impl Paused {
+    // Las repeticiones no se pueden guardar como partida
+    pub fn new(can_save: bool) -> Paused {
+        let mut options = vec![PauseOption::Resume, PauseOption::Restart, PauseOption::Quit];
+        if can_save {
+            options.insert(2, PauseOption::SaveAndQuit);
+        }
+        Paused { options, selected: 0 }
+    }
+}
+
+impl Scene for Paused {
+    fn draw(&mut self, ctx: &mut Context, _shared: &Shared) -> GameResult<()> {
+        draw_dim(ctx)?;
+        draw_centered_text(ctx, "PAUSA", 48.0, 220.0, Color::GREEN)?;
+        let options: Vec<String> = self.options.iter().map(|option| option.label().to_string()).collect();
+        draw_menu(ctx, &options, self.selected, 320.0)?;
+        draw_centered_text(ctx, "Esc o P: continuar", 20.0, 500.0, Color::WHITE)
+    }
+
+    fn key_down(&mut self, keycode: KeyCode, shared: &mut Shared) -> Transition {
+        match keycode {
+            KeyCode::P | KeyCode::Escape => Transition::Pop,
+            KeyCode::Return | KeyCode::Space => match self.options[self.selected] {
+                PauseOption::Resume => Transition::Pop,
+                PauseOption::Restart => Transition::Reset(Box::new(Playing::new(shared))),
+                PauseOption::SaveAndQuit => {
+                    // La partida se guarda al salir de la pila, en `Playing::exit`
+                    shared.save_on_exit = true;
+                    Transition::Reset(Box::new(Title::default()))
+                }
+                PauseOption::Quit => Transition::Quit,
+            },
+            _ => {
+                self.selected = move_selection(self.selected, self.options.len(), keycode);
+                Transition::None
+            }
+        }
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+}
+
+// Iniciales al estilo arcade: arriba/abajo cambian la letra, izquierda/derecha
+// mueven el cursor y Enter confirma
+pub struct NameEntry {
+    summary: RunSummary,
+    letters: [u8; NAME_LENGTH],
+    cursor: usize,
+}
+
+impl NameEntry {
+    pub fn new(world: &World) -> NameEntry {
+        NameEntry {
+            summary: RunSummary::new(world),
+            letters: [b'A'; NAME_LENGTH],
+            cursor: 0,
+        }
+    }
+
+    fn name(&self) -> String {
+        self.letters.iter().map(|&letter| letter as char).collect()
+    }
+}
+
+impl Scene for NameEntry {
+    fn draw(&mut self, ctx: &mut Context, _shared: &Shared) -> GameResult<()> {
+        draw_dim(ctx)?;
+        draw_centered_text(ctx, "¡Nuevo récord!", 48.0, 200.0, Color::GREEN)?;
+        draw_centered_text(ctx, &format!("Puntuación: {}", self.summary.score), 28.0, 270.0, Color::WHITE)?;
+        draw_centered_text(ctx, "Escribe tus iniciales", 24.0, 330.0, Color::WHITE)?;
+
+        // La letra que se está cambiando va entre corchetes
+        let name: Vec<String> = self
+            .letters
+            .iter()
+            .enumerate()
+            .map(|(i, &letter)| if i == self.cursor { format!("[{}]", letter as char) } else { (letter as char).to_string() })
+            .collect();
+        draw_centered_text(ctx, &name.join(" "), 48.0, 380.0, Color::YELLOW)
+    }
+
+    fn key_down(&mut self, keycode: KeyCode, shared: &mut Shared) -> Transition {
+        let letter = &mut self.letters[self.cursor];
+        match keycode {
+            KeyCode::Up => *letter = if *letter == b'Z' { b'A' } else { *letter + 1 },
+            KeyCode::Down => *letter = if *letter == b'A' { b'Z' } else { *letter - 1 },
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(NAME_LENGTH - 1),
+            KeyCode::Return => {
+                let summary = &self.summary;
+                let entry = HighScore::new(&self.name(), summary.score, summary.level, summary.hits, summary.seed);
+                let rank = shared.high_scores.insert(entry);
+                shared.save_high_scores();
+                let game_over = GameOver::from_summary(self.summary.clone(), rank, false);
+                return Transition::Replace(Box::new(game_over));
+            }
+            _ => (),
+        }
+        Transition::None
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+}
+
+pub struct GameOver {
+    summary: RunSummary,
+    // Puesto en la tabla, si la partida ha entrado
+    rank: Option<usize>,
+    // La partida era una repetición que ha llegado al final
+    replay: bool,
+}
+
+impl GameOver {
+    pub fn new(world: &World, rank: Option<usize>, replay: bool) -> GameOver {
+        GameOver::from_summary(RunSummary::new(world), rank, replay)
+    }
+
+    fn from_summary(summary: RunSummary, rank: Option<usize>, replay: bool) -> GameOver {
+        GameOver { summary, rank, replay }
+    }
+}
+
+impl Scene for GameOver {
+    fn draw(&mut self, ctx: &mut Context, shared: &Shared) -> GameResult<()> {
+        let summary = &self.summary;
+        let title = if self.replay { "Fin de la repetición" } else { "Game Over" };
+        draw_dim(ctx)?;
+        draw_centered_text(ctx, title, 48.0, 90.0, Color::GREEN)?;
+        draw_centered_text(
+            ctx,
+            &format!("Puntuación: {}   Nivel: {}   Hits: {}", summary.score, summary.level, summary.hits),
+            24.0,
+            160.0,
+            Color::WHITE,
+        )?;
+        draw_centered_text(ctx, &format!("Semilla: {}", summary.seed), 20.0, 192.0, Color::WHITE)?;
+        draw_high_scores(ctx, &shared.high_scores, self.rank, 250.0)?;
+        draw_centered_text(ctx, "R: nueva partida   Esc: menú", 24.0, 600.0, Color::YELLOW)
+    }
+
+    fn key_down(&mut self, keycode: KeyCode, shared: &mut Shared) -> Transition {
+        match keycode {
+            KeyCode::R | KeyCode::Return => Transition::Reset(Box::new(Playing::new(shared))),
+            KeyCode::Escape => Transition::Reset(Box::new(Title::default())),
+            _ => Transition::None,
+        }
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,281 @@
+// Front-end de ggez organizado como una pila de escenas. Solo la escena de
+// arriba recibe la entrada y avanza; las superpuestas (pausa, fin de partida)
+// se dibujan encima de la que tienen debajo, que queda congelada.
+mod menus;
+mod playing;
+mod render;
+
+use ggez::event::{self, EventHandler, KeyCode, KeyMods};
+use ggez::graphics::{self, Color, Font, Text};
+use ggez::{Context, GameResult};
+use std::path::PathBuf;
+use std::time::Instant;
+
+use bubbleroid::constants::*;
+use bubbleroid::{Config, ConfigWatcher, HighScores, World};
+
+pub use menus::Title;
+pub use playing::Playing;
+
+const MESSAGE_TIME: f32 = 3.0;
+
+pub fn print_replay_result(world: &World) {
+    println!(
+        "Repetición terminada: puntuación {}, nivel {}, hits {}",
+        world.score, world.level, world.destroyed_count
+    );
+}
+
+struct Message {
+    text: String,
+    is_error: bool,
+    shown_at: Instant,
+}
+
+// Estado común a todas las escenas
+pub struct Shared {
+    // Configuración para las partidas nuevas; la partida en curso la recoge
+    // cuando cambia `config_generation`
+    pub config: Config,
+    pub config_generation: u64,
+    pub seed: Option<u64>,
+    pub record_path: PathBuf,
+    pub high_scores: HighScores,
+    pub high_scores_path: PathBuf,
+    pub save_path: PathBuf,
+    // Hay una partida guardada para continuar desde el título
+    pub has_save: bool,
+    // La partida en curso se guarda al salir en vez de darse por terminada
+    pub save_on_exit: bool,
+    // Escribir energía y momento de los asteroides en cada tick (--diagnostics)
+    pub diagnostics: bool,
+    config_watcher: Option<ConfigWatcher>,
+    // Último aviso (recarga de la configuración, partida guardada...); los errores se quedan en pantalla
+    message: Option<Message>,
+}
+
+impl Shared {
+    pub fn new(
+        config: Config,
+        config_path: Option<PathBuf>,
+        seed: Option<u64>,
+        record_path: PathBuf,
+        high_scores_path: PathBuf,
+        save_path: PathBuf,
+    ) -> Shared {
+        let high_scores = HighScores::load_or_empty(&high_scores_path, |err| {
+            eprintln!("no se pudo leer {}: {}", high_scores_path.display(), err);
+        });
+        Shared {
+            config,
+            config_generation: 0,
+            seed,
+            record_path,
+            high_scores,
+            high_scores_path,
+            has_save: save_path.exists(),
+            save_path,
+            save_on_exit: false,
+            diagnostics: false,
+            config_watcher: config_path.map(ConfigWatcher::new),
+            message: None,
+        }
+    }
+
+    pub fn show_message(&mut self, text: String, is_error: bool) {
+        self.message = Some(Message {
+            text,
+            is_error,
+            shown_at: Instant::now(),
+        });
+    }
+
+    pub fn save_high_scores(&self) {
+        if let Err(err) = self.high_scores.save(&self.high_scores_path) {
+            eprintln!("no se pudo guardar {}: {}", self.high_scores_path.display(), err);
+        }
+    }
+
+    pub fn set_config(&mut self, config: Config) {
+        self.config = config;
+        self.config_generation += 1;
+    }
+
+    // Recarga en caliente: una configuración válida se aplica en el siguiente tick,
+    // una no válida se muestra en pantalla y se sigue usando la anterior
+    fn poll_config(&mut self) {
+        let Some(watcher) = &mut self.config_watcher else {
+            return;
+        };
+        match watcher.reload(&mut self.config) {
+            Some(Ok(())) => {
+                let text = format!("Configuración recargada: {}", watcher.path().display());
+                self.config_generation += 1;
+                self.show_message(text, false);
+            }
+            Some(Err(err)) => self.show_message(err.to_string(), true),
+            None => {}
+        }
+    }
+
+    fn draw_message(&self, ctx: &mut Context) -> GameResult<()> {
+        if let Some(message) = &self.message {
+            if message.is_error || message.shown_at.elapsed().as_secs_f32() < MESSAGE_TIME {
+                let color = if message.is_error { Color::RED } else { Color::GREEN };
+                let text = Text::new((message.text.as_str(), Font::default(), 20.0));
+                graphics::draw(ctx, &text, (ggez::mint::Point2 { x: 10.0, y: WINDOW_HEIGHT - 30.0 }, 0.0, color))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub enum Transition {
+    None,
+    Push(Box<dyn Scene>),
+    Pop,
+    // Sustituye la escena de arriba
+    Replace(Box<dyn Scene>),
+    // Vacía la pila y empieza de nuevo con la escena dada
+    Reset(Box<dyn Scene>),
+    Quit,
+}
+
+pub trait Scene {
+    // Se llama una vez por frame, solo en la escena de arriba
+    fn update(&mut self, _shared: &mut Shared) -> Transition {
+        Transition::None
+    }
+
+    fn draw(&mut self, ctx: &mut Context, shared: &Shared) -> GameResult<()>;
+
+    fn key_down(&mut self, keycode: KeyCode, shared: &mut Shared) -> Transition;
+
+    fn key_up(&mut self, _keycode: KeyCode) {}
+
+    // La ventana ha perdido el foco
+    fn focus_lost(&mut self) -> Transition {
+        Transition::None
+    }
+
+    // La escena vuelve a estar arriba al quitar la que tenía encima
+    fn resume(&mut self) {}
+
+    // Una escena superpuesta deja ver la que tiene debajo
+    fn is_overlay(&self) -> bool {
+        false
+    }
+
+    // Al sacar la escena de la pila o cerrar el juego, para guardar lo que haga falta
+    fn exit(&mut self, _shared: &mut Shared) {}
+}
+
+pub struct SceneStack {
+    scenes: Vec<Box<dyn Scene>>,
+    shared: Shared,
+}
+
+impl SceneStack {
+    pub fn new(shared: Shared, first: Box<dyn Scene>) -> SceneStack {
+        SceneStack { scenes: vec![first], shared }
+    }
+
+    fn apply(&mut self, ctx: &mut Context, transition: Transition) {
+        match transition {
+            Transition::None => {}
+            Transition::Push(scene) => self.scenes.push(scene),
+            Transition::Pop => {
+                self.pop();
+                if let Some(scene) = self.scenes.last_mut() {
+                    scene.resume();
+                }
+            }
+            Transition::Replace(scene) => {
+                self.pop();
+                self.scenes.push(scene);
+            }
+            Transition::Reset(scene) => {
+                self.clear();
+                self.scenes.push(scene);
+            }
+            Transition::Quit => {
+                self.clear();
+                event::quit(ctx);
+            }
+        }
+        if self.scenes.is_empty() {
+            event::quit(ctx);
+        }
+    }
+
+    fn pop(&mut self) {
+        if let Some(mut scene) = self.scenes.pop() {
+            scene.exit(&mut self.shared);
+        }
+    }
+
+    fn clear(&mut self) {
+        while !self.scenes.is_empty() {
+            self.pop();
+        }
+    }
+}
+
+impl EventHandler for SceneStack {
+    fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
+        self.shared.poll_config();
+        let transition = match self.scenes.last_mut() {
+            Some(scene) => scene.update(&mut self.shared),
+            None => Transition::None,
+        };
+        self.apply(ctx, transition);
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+        graphics::clear(ctx, Color::from_rgb(1, 4, 0));
+
+        // Desde la primera escena opaca hasta arriba
+        let mut first = self.scenes.len().saturating_sub(1);
+        while first > 0 && self.scenes[first].is_overlay() {
+            first -= 1;
+        }
+        for scene in &mut self.scenes[first..] {
+            scene.draw(ctx, &self.shared)?;
+        }
+
+        self.shared.draw_message(ctx)?;
+        graphics::present(ctx)?;
+        Ok(())
+    }
+
+    fn key_down_event(&mut self, ctx: &mut Context, keycode: KeyCode, _keymods: KeyMods, _repeat: bool) {
+        let transition = match self.scenes.last_mut() {
+            Some(scene) => scene.key_down(keycode, &mut self.shared),
+            None => Transition::None,
+        };
+        self.apply(ctx, transition);
+    }
+
+    fn key_up_event(&mut self, _ctx: &mut Context, keycode: KeyCode, _keymods: KeyMods) {
+        if let Some(scene) = self.scenes.last_mut() {
+            scene.key_up(keycode);
+        }
+    }
+
+    fn focus_event(&mut self, ctx: &mut Context, gained: bool) {
+        if gained {
+            return;
+        }
+        let transition = match self.scenes.last_mut() {
+            Some(scene) => scene.focus_lost(),
+            None => Transition::None,
+        };
+        self.apply(ctx, transition);
+    }
+
+    fn quit_event(&mut self, _ctx: &mut Context) -> bool {
+        self.clear();
+        false
+    }
+}
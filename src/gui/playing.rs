@@ -0,0 +1,221 @@
+use ggez::event::KeyCode;
+use ggez::{Context, GameResult};
+use std::time::Instant;
+
+use bubbleroid::{Config, FixedTimestep, Input, Recorder, Replay, ReplayInputs, SaveGame, World};
+
+use super::menus::{GameOver, NameEntry, Paused};
+use super::render::draw_world;
+use super::{print_replay_result, Scene, Shared, Transition};
+
+// Una partida en curso, jugada con el teclado o reproducida desde una repetición
+pub struct Playing {
+    world: World,
+    input: Input,
+    timestep: FixedTimestep,
+    last_update: Instant,
+    // Solo las partidas jugadas se graban; las repeticiones traen su entrada
+    recorder: Option<Recorder>,
+    playback: Option<ReplayInputs>,
+    config_generation: u64,
+    // Una partida recuperada empieza en pausa para que el jugador se sitúe
+    start_paused: bool,
+}
+
+impl Playing {
+    // Partida nueva con la semilla fija de --seed o una aleatoria
+    pub fn new(shared: &Shared) -> Playing {
+        let seed = shared.seed.unwrap_or_else(rand::random);
+        Playing {
+            world: World::new(seed, shared.config.clone()),
+            input: Input::default(),
+            timestep: FixedTimestep::default(),
+            last_update: Instant::now(),
+            recorder: Some(Recorder::new(seed, shared.config.clone())),
+            playback: None,
+            config_generation: shared.config_generation,
+            start_paused: false,
+        }
+    }
+
+    // Continúa una partida guardada, que se sigue grabando en la misma repetición
+    pub fn from_save(save: SaveGame, shared: &Shared) -> Playing {
+        Playing {
+            world: save.world,
+            input: Input::default(),
+            timestep: FixedTimestep::default(),
+            last_update: Instant::now(),
+            recorder: Some(Recorder::resume(save.replay)),
+            playback: None,
+            config_generation: shared.config_generation,
+            start_paused: true,
+        }
+    }
+
+    pub fn from_replay(replay: Replay, shared: &Shared) -> Playing {
+        Playing {
+            world: World::new(replay.seed, replay.config.clone()),
+            input: Input::default(),
+            timestep: FixedTimestep::default(),
+            last_update: Instant::now(),
+            recorder: None,
+            playback: Some(replay.into_inputs()),
+            config_generation: shared.config_generation,
+            start_paused: false,
+        }
+    }
+
+    // Entrada del siguiente tick y configuración nueva si la hay: la de la
+    // repetición o la del teclado y el fichero, que quedan grabadas
+    fn next_input(&mut self, shared: &Shared) -> Option<(Input, Option<Config>)> {
+        if let Some(playback) = &mut self.playback {
+            return playback.next();
+        }
+
+        // Las teclas mantenidas siguen activas; las pulsaciones solo duran un tick
+        let input = self.input;
+        self.input.fire = false;
+
+        let mut config = None;
+        if self.config_generation != shared.config_generation {
+            self.config_generation = shared.config_generation;
+            config = Some(shared.config.clone());
+        }
+
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(self.world.tick, &input);
+            if let Some(config) = &config {
+                recorder.record_config(self.world.tick, config);
+            }
+        }
+        Some((input, config))
+    }
+
+    fn pause(&mut self) -> Transition {
+        // Las teclas que se suelten durante la pausa no llegarían a esta escena
+        self.input = Input::default();
+        Transition::Push(Box::new(Paused::new(self.recorder.is_some())))
+    }
+
+    fn save_replay(&self, shared: &Shared) {
+        if let Some(recorder) = &self.recorder {
+            if let Err(err) = recorder.finish().save(&shared.record_path) {
+                eprintln!("no se pudo guardar la repetición en {}: {}", shared.record_path.display(), err);
+            }
+        }
+    }
+
+    fn save_game(&self, shared: &mut Shared) {
+        let Some(recorder) = &self.recorder else {
+            return;
+        };
+        let save = SaveGame {
+            world: self.world.clone(),
+            replay: recorder.finish(),
+        };
+        match save.save(&shared.save_path) {
+            Ok(()) => {
+                shared.has_save = true;
+                shared.show_message("Partida guardada".to_string(), false);
+            }
+            Err(err) => {
+                let text = format!("no se pudo guardar la partida en {}: {}", shared.save_path.display(), err);
+                shared.show_message(text, true);
+            }
+        }
+    }
+
+    // Escena que se abre encima al acabar la partida
+    fn finish(&mut self, shared: &Shared) -> Transition {
+        self.input = Input::default();
+        if self.playback.is_some() {
+            print_replay_result(&self.world);
+            return Transition::Push(Box::new(GameOver::new(&self.world, None, true)));
+        }
+
+        self.save_replay(shared);
+        if shared.high_scores.qualifies(self.world.score) {
+            Transition::Push(Box::new(NameEntry::new(&self.world)))
+        } else {
+            Transition::Push(Box::new(GameOver::new(&self.world, None, false)))
+        }
+    }
+}
+
+impl Scene for Playing {
+    fn update(&mut self, shared: &mut Shared) -> Transition {
+        if self.start_paused {
+            self.start_paused = false;
+            return self.pause();
+        }
+
+        let dt = self.last_update.elapsed().as_secs_f32();
+        self.last_update = Instant::now();
+
+        // La simulación avanza a paso fijo; la entrada pendiente se aplica en el primer paso
+        for _ in 0..self.timestep.advance(dt) {
+            let Some((input, config)) = self.next_input(shared) else {
+                return self.finish(shared);
+            };
+            if let Some(config) = config {
+                self.world.config = config;
+            }
+            self.world.step(self.timestep.dt(), &input);
+            if shared.diagnostics {
+                println!("{}", self.world.diagnostics());
+            }
+
+            if self.world.is_game_over {
+                return self.finish(shared);
+            }
+        }
+
+        Transition::None
+    }
+
+    fn draw(&mut self, ctx: &mut Context, _shared: &Shared) -> GameResult<()> {
+        draw_world(ctx, &self.world, self.timestep.alpha())
+    }
+
+    fn key_down(&mut self, keycode: KeyCode, _shared: &mut Shared) -> Transition {
+        match keycode {
+            KeyCode::Up => self.input.thrust = true,
+            KeyCode::Left => self.input.rotate_left = true,
+            KeyCode::Right => self.input.rotate_right = true,
+            KeyCode::Space => self.input.fire = true,
+            KeyCode::P | KeyCode::Escape => return self.pause(),
+            _ => (),
+        }
+        Transition::None
+    }
+
+    fn focus_lost(&mut self) -> Transition {
+        self.pause()
+    }
+
+    // El reloj no corre durante la pausa: el tiempo pasado en ella no se simula
+    fn resume(&mut self) {
+        self.last_update = Instant::now();
+    }
+
+    fn key_up(&mut self, keycode: KeyCode) {
+        match keycode {
+            KeyCode::Up => self.input.thrust = false,
+            KeyCode::Left => self.input.rotate_left = false,
+            KeyCode::Right => self.input.rotate_right = false,
+            _ => (),
+        }
+    }
+
+    fn exit(&mut self, shared: &mut Shared) {
+        if shared.save_on_exit {
+            shared.save_on_exit = false;
+            self.save_game(shared);
+            return;
+        }
+        // Una partida abandonada también se guarda; si terminó, se guardó al acabar
+        if !self.world.is_game_over {
+            self.save_replay(shared);
+        }
+    }
+}
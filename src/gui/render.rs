@@ -0,0 +1,199 @@
+use ggez::graphics::{self, Color, DrawMode, Font, Mesh, Text};
+use ggez::{Context, GameResult};
+
+use bubbleroid::constants::*;
+use bubbleroid::{interpolate_position, wrap_position, Asteroid, Rgba, World};
+
+pub fn to_color(color: Rgba) -> Color {
+    Color::from_rgba(color.0, color.1, color.2, color.3)
+}
+
+pub fn draw_text(ctx: &mut Context, text: &str, size: f32, x: f32, y: f32, color: Color) -> GameResult<()> {
+    let text = Text::new((text, Font::default(), size));
+    graphics::draw(ctx, &text, (ggez::mint::Point2 { x, y }, 0.0, color))
+}
+
+// Texto centrado en horizontal a la altura `y`
+pub fn draw_centered_text(ctx: &mut Context, text: &str, size: f32, y: f32, color: Color) -> GameResult<()> {
+    let text = Text::new((text, Font::default(), size));
+    let x = (WINDOW_WIDTH - text.width(ctx)) / 2.0;
+    graphics::draw(ctx, &text, (ggez::mint::Point2 { x, y }, 0.0, color))
+}
+
+// Oscurece lo que haya debajo de una escena superpuesta
+pub fn draw_dim(ctx: &mut Context) -> GameResult<()> {
+    let rect = graphics::Rect::new(0.0, 0.0, WINDOW_WIDTH, WINDOW_HEIGHT);
+    let mesh = Mesh::new_rectangle(ctx, DrawMode::fill(), rect, Color::from_rgba(0, 0, 0, 160))?;
+    graphics::draw(ctx, &mesh, graphics::DrawParam::default())
+}
+
+pub fn create_player_mesh(ctx: &mut Context) -> GameResult<Mesh> {
+    let player_points = SHIP_POINTS.map(|(x, y)| [x, y]);
+    Mesh::new_polygon(
+        ctx,
+        DrawMode::stroke(1.0),
+        &player_points,
+        // Make an orange
+        Color::from_rgb(200, 140, 0)
+    )
+}
+
+// Desplazamientos con los que hay que dibujar un objeto de radio `radius` en
+// `pos`: el propio sitio y, si se sale por algún borde, copias en el lado
+// contrario (cuatro en una esquina), para que cruce el borde sin saltos
+fn wrap_offsets(pos: (f32, f32), radius: f32) -> Vec<(f32, f32)> {
+    let xs = axis_offsets(pos.0, radius, WINDOW_WIDTH);
+    let ys = axis_offsets(pos.1, radius, WINDOW_HEIGHT);
+    xs.iter().flat_map(|&x| ys.iter().map(move |&y| (x, y))).collect()
+}
+
+fn axis_offsets(coord: f32, radius: f32, length: f32) -> Vec<f32> {
+    let mut offsets = vec![0.0];
+    if coord - radius < 0.0 {
+        offsets.push(length);
+    }
+    if coord + radius > length {
+        offsets.push(-length);
+    }
+    offsets
+}
+
+// Dibuja `drawable` centrado en `pos` y en sus copias al otro lado de los bordes
+fn draw_wrapped<D: graphics::Drawable>(
+    ctx: &mut Context,
+    drawable: &D,
+    pos: (f32, f32),
+    radius: f32,
+    param: graphics::DrawParam,
+) -> GameResult<()> {
+    for (dx, dy) in wrap_offsets(pos, radius) {
+        graphics::draw(ctx, drawable, param.dest([pos.0 + dx, pos.1 + dy]))?;
+    }
+    Ok(())
+}
+
+fn create_deformed_asteroid_mesh(ctx: &mut Context, asteroid: &Asteroid) -> GameResult<Mesh> {
+    let points: Vec<[f32; 2]> = asteroid.outline().into_iter().map(|(x, y)| [x, y]).collect();
+
+    Mesh::new_polygon(
+        ctx,
+        DrawMode::stroke(1.0),
+        &points,
+        Color::GREEN,
+    )
+}
+
+// Las partículas no dan la vuelta en la simulación; al dibujarlas sí
+fn draw_particle(ctx: &mut Context, pos: (f32, f32), color: Rgba) -> GameResult<()> {
+    let particle_mesh = Mesh::new_circle(
+        ctx,
+        DrawMode::fill(),
+        ggez::mint::Point2 { x: 0.0, y: 0.0 },
+        PARTICLE_SIZE,
+        0.1,
+        to_color(color),
+    )?;
+    draw_wrapped(ctx, &particle_mesh, wrap_position(pos), PARTICLE_SIZE, graphics::DrawParam::default())
+}
+
+fn draw_particles(ctx: &mut Context, world: &World) -> GameResult<()> {
+    for particle in &world.particles {
+        draw_particle(ctx, particle.pos, particle.color)?;
+    }
+    Ok(())
+}
+
+// Puntos de la trayectoria prevista de la próxima bala, cada vez más tenues
+fn draw_aim_preview(ctx: &mut Context, world: &World) -> GameResult<()> {
+    let path = world.predict_bullet_path(world.config.bullets.aim_preview);
+    let dot = Mesh::new_circle(ctx, DrawMode::fill(), [0.0, 0.0], 1.5, 0.1, Color::WHITE)?;
+    for (i, pos) in path.iter().enumerate().step_by(4) {
+        let fade = 1.0 - i as f32 / path.len() as f32;
+        let color = Color::new(1.0, 1.0, 0.0, 0.6 * fade);
+        graphics::draw(ctx, &dot, graphics::DrawParam::default().dest([pos.0, pos.1]).color(color))?;
+    }
+    Ok(())
+}
+
+fn draw_score(ctx: &mut Context, world: &World) -> GameResult<()> {
+    let font = Font::default();
+    let score_text = Text::new((format!("Hits: {}", world.destroyed_count), font, 26.0));
+    let (width, _) = graphics::drawable_size(ctx);
+    let dest_point = ggez::mint::Point2 { x: width - 200.0, y: 20.0 };
+    graphics::draw(ctx, &score_text, (dest_point, 0.0, Color::GREEN))?;
+    Ok(())
+}
+
+fn draw_hud(ctx: &mut Context, world: &World) -> GameResult<()> {
+    let font = Font::default();
+    let score_text = Text::new((format!("Puntuación: {}", world.score), font, 26.0));
+    let level_text = Text::new((format!("Nivel: {}", world.level), font, 26.0));
+
+    graphics::draw(ctx, &score_text, (ggez::mint::Point2 { x: 10.0, y: 10.0 }, 0.0, Color::GREEN))?;
+    graphics::draw(ctx, &level_text, (ggez::mint::Point2 { x: 10.0, y: 40.0 }, 0.0, Color::GREEN))?;
+
+    // Una nave pequeña por cada vida restante
+    let life_mesh = create_player_mesh(ctx)?;
+    for i in 0..world.lives {
+        let draw_param = graphics::DrawParam::default()
+            .dest([20.0 + i as f32 * 22.0, 90.0])
+            .offset([0.5, 0.5])
+            .scale([0.7, 0.7]);
+        graphics::draw(ctx, &life_mesh, draw_param)?;
+    }
+
+    Ok(())
+}
+
+// Dibuja la partida interpolando las posiciones entre los dos últimos ticks
+pub fn draw_world(ctx: &mut Context, world: &World, alpha: f32) -> GameResult<()> {
+    // Dibujar partículas del propulsor
+    for particle in &world.thruster_particles {
+        draw_particle(ctx, particle.pos, particle.color)?;
+    }
+
+    let bullets = &world.config.bullets;
+    if bullets.gravity && bullets.aim_preview > 0.0 && world.lives > 0 {
+        draw_aim_preview(ctx, world)?;
+    }
+
+    // Draw player ship as a triangle; it blinks while invulnerable
+    let blink_off = world.is_invulnerable() && (world.invulnerable_time * 8.0) as u32 % 2 == 1;
+    if !blink_off && world.lives > 0 {
+        let player_mesh = create_player_mesh(ctx)?;
+        let player_pos = interpolate_position(world.player_prev_pos, world.player_pos, alpha);
+        let draw_param = graphics::DrawParam::default()
+            .rotation(world.player_angle + std::f32::consts::FRAC_PI_2)
+            .offset([0.5, 0.5]);
+        draw_wrapped(ctx, &player_mesh, player_pos, 1.5 * PLAYER_SIZE, draw_param)?;
+    }
+
+    for bullet in &world.bullets {
+        let pos = interpolate_position(bullet.prev_pos, bullet.pos, alpha);
+        let bullet_mesh = Mesh::new_circle(
+            ctx,
+            DrawMode::fill(),
+            [0.0, 0.0],
+            BULLET_SIZE,
+            0.1,
+            Color::YELLOW,
+        )?;
+        draw_wrapped(ctx, &bullet_mesh, pos, BULLET_SIZE, graphics::DrawParam::default())?;
+    }
+
+    for asteroid in &world.asteroids {
+        // El contorno está en coordenadas del mundo: se desplaza hasta la
+        // posición interpolada y a las copias del otro lado de los bordes
+        let pos = interpolate_position(asteroid.prev_pos, asteroid.pos, alpha);
+        let asteroid_mesh = create_deformed_asteroid_mesh(ctx, asteroid)?;
+        for (dx, dy) in wrap_offsets(pos, asteroid.bounding_radius()) {
+            let draw_param = graphics::DrawParam::default()
+                .dest([pos.0 - asteroid.pos.0 + dx, pos.1 - asteroid.pos.1 + dy]);
+            graphics::draw(ctx, &asteroid_mesh, draw_param)?;
+        }
+    }
+
+    draw_particles(ctx, world)?;
+    draw_score(ctx, world)?;
+    draw_hud(ctx, world)
+}
@@ -0,0 +1,206 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+// Puestos que se guardan en la tabla
+pub const HIGH_SCORE_COUNT: usize = 10;
+pub const NAME_LENGTH: usize = 3;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HighScore {
+    pub name: String,
+    pub score: u32,
+    pub level: u32,
+    pub hits: f32,
+    #[serde(with = "crate::serde_text")]
+    pub seed: u64,
+    // Fecha de la partida como AAAA-MM-DD
+    pub date: String,
+}
+
+// Mejores puntuaciones, de mayor a menor
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HighScores {
+    entries: Vec<HighScore>,
+}
+
+#[derive(Debug)]
+pub enum HighScoreError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+}
+
+impl fmt::Display for HighScoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HighScoreError::Io(err) => write!(f, "error de lectura/escritura: {}", err),
+            HighScoreError::Parse(err) => write!(f, "tabla de puntuaciones mal formada: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for HighScoreError {}
+
+// Días desde 1970-01-01 a fecha civil (algoritmo de Howard Hinnant)
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+fn today() -> String {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+impl HighScore {
+    // Puntuación con la fecha de hoy
+    pub fn new(name: &str, score: u32, level: u32, hits: f32, seed: u64) -> HighScore {
+        HighScore {
+            name: name.to_string(),
+            score,
+            level,
+            hits,
+            seed,
+            date: today(),
+        }
+    }
+}
+
+impl HighScores {
+    // Un fichero que no existe es una tabla vacía
+    pub fn load(path: impl AsRef<Path>) -> Result<HighScores, HighScoreError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(HighScores::default()),
+            Err(err) => return Err(HighScoreError::Io(err)),
+        };
+        let mut scores: HighScores = toml::from_str(&text).map_err(HighScoreError::Parse)?;
+
+        // El fichero puede estar editado a mano: se reordena y se recorta
+        scores.entries.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+        scores.entries.truncate(HIGH_SCORE_COUNT);
+        Ok(scores)
+    }
+
+    // Una tabla ilegible no impide jugar: se empieza con una vacía y se pasa el
+    // error a `warn` para avisar
+    pub fn load_or_empty(path: impl AsRef<Path>, warn: impl FnOnce(HighScoreError)) -> HighScores {
+        HighScores::load(path).unwrap_or_else(|err| {
+            warn(err);
+            HighScores::default()
+        })
+    }
+
+    // Escribe en un fichero temporal y lo renombra, para no dejar la tabla a medias
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = toml::to_string(self).expect("la tabla siempre se puede serializar");
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, text)?;
+        fs::rename(&tmp, path)
+    }
+
+    pub fn entries(&self) -> &[HighScore] {
+        &self.entries
+    }
+
+    // Si la puntuación entraría en la tabla
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0 && (self.entries.len() < HIGH_SCORE_COUNT || self.entries.iter().any(|other| score > other.score))
+    }
+
+    // Inserta la puntuación y devuelve su puesto, o None si no entra. Con un
+    // empate, la puntuación más antigua queda por delante.
+    pub fn insert(&mut self, entry: HighScore) -> Option<usize> {
+        if !self.qualifies(entry.score) {
+            return None;
+        }
+        let rank = self.entries.iter().position(|other| entry.score > other.score).unwrap_or(self.entries.len());
+        self.entries.insert(rank, entry);
+        self.entries.truncate(HIGH_SCORE_COUNT);
+        Some(rank)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn entry(name: &str, score: u32) -> HighScore {
+        HighScore::new(name, score, 1, 0.5, 42)
+    }
+
+    // Fichero propio de cada prueba, para que puedan correr a la vez
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("bubbleroid-{}-{}.toml", name, std::process::id()))
+    }
+
+    #[test]
+    fn only_the_top_scores_are_kept() {
+        let mut scores = HighScores::default();
+        for score in 1..=HIGH_SCORE_COUNT as u32 + 2 {
+            scores.insert(entry("AAA", score * 100));
+        }
+        assert_eq!(scores.entries().len(), HIGH_SCORE_COUNT);
+        assert_eq!(scores.entries()[0].score, (HIGH_SCORE_COUNT as u32 + 2) * 100);
+        assert_eq!(scores.entries()[HIGH_SCORE_COUNT - 1].score, 300);
+        assert!(!scores.qualifies(300));
+        assert_eq!(scores.insert(entry("BBB", 200)), None);
+
+        // Un fichero editado a mano con más puestos se recorta al cargarlo
+        let path = temp_path("top");
+        let mut entries = scores.entries().to_vec();
+        entries.push(entry("CCC", 50));
+        fs::write(&path, toml::to_string(&HighScores { entries }).unwrap()).unwrap();
+        let loaded = HighScores::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, scores);
+    }
+
+    #[test]
+    fn ties_keep_the_older_entry_first() {
+        let mut scores = HighScores::default();
+        scores.insert(entry("OLD", 500));
+        scores.insert(entry("TOP", 900));
+        assert_eq!(scores.insert(entry("NEW", 500)), Some(2));
+        let names: Vec<&str> = scores.entries().iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(names, ["TOP", "OLD", "NEW"]);
+    }
+
+    #[test]
+    fn missing_or_corrupt_file_loads_as_an_empty_table() {
+        let path = temp_path("missing");
+        let _ = fs::remove_file(&path);
+        let scores = HighScores::load_or_empty(&path, |err| panic!("un fichero que falta no es un error: {}", err));
+        assert!(scores.entries().is_empty());
+
+        let path = temp_path("corrupt");
+        fs::write(&path, "entries = \"no es una tabla\"").unwrap();
+        let mut warned = false;
+        let scores = HighScores::load_or_empty(&path, |err| {
+            assert!(matches!(err, HighScoreError::Parse(_)), "{}", err);
+            warned = true;
+        });
+        fs::remove_file(&path).unwrap();
+        assert!(scores.entries().is_empty());
+        assert!(warned, "no se ha avisado del fichero corrupto");
+    }
+}
@@ -0,0 +1,87 @@
+use crate::config::{Integrator, PhysicsConfig};
+use crate::entities::Asteroid;
+use crate::physics::{clamp_magnitude, gravity_forces, wrap_position};
+
+// Aceleración de cada asteroide con las posiciones dadas. Los asteroides
+// aceleran con la fuerza tal cual (su masa solo cuenta como carga
+// gravitatoria) y el módulo se limita sin cambiar la dirección.
+fn accelerations(physics: &PhysicsConfig, max_acceleration: f32, bodies: &[((f32, f32), f32)]) -> Vec<(f32, f32)> {
+    gravity_forces(physics, bodies)
+        .into_iter()
+        .map(|force| clamp_magnitude(force, max_acceleration))
+        .collect()
+}
+
+// Cuerpos desplazados `offsets * scale` desde su posición actual
+fn shifted(asteroids: &[Asteroid], offsets: &[(f32, f32)], scale: f32) -> Vec<((f32, f32), f32)> {
+    asteroids
+        .iter()
+        .zip(offsets)
+        .map(|(asteroid, offset)| {
+            let pos = (asteroid.pos.0 + offset.0 * scale, asteroid.pos.1 + offset.1 * scale);
+            (pos, asteroid.mass)
+        })
+        .collect()
+}
+
+fn bodies(asteroids: &[Asteroid]) -> Vec<((f32, f32), f32)> {
+    asteroids.iter().map(|asteroid| (asteroid.pos, asteroid.mass)).collect()
+}
+
+// Avanza `dt` segundos la posición y la velocidad de los asteroides bajo su
+// gravedad mutua con el integrador de la configuración. Las reglas del juego
+// (velocidad máxima, choques) se aplican aparte, después.
+pub fn integrate(physics: &PhysicsConfig, max_acceleration: f32, asteroids: &mut [Asteroid], dt: f32) {
+    match physics.integrator {
+        Integrator::SemiImplicitEuler => {
+            let acceleration = accelerations(physics, max_acceleration, &bodies(asteroids));
+            for (asteroid, a) in asteroids.iter_mut().zip(acceleration) {
+                asteroid.vel.0 += a.0 * dt;
+                asteroid.vel.1 += a.1 * dt;
+                asteroid.pos.0 += asteroid.vel.0 * dt;
+                asteroid.pos.1 += asteroid.vel.1 * dt;
+            }
+        }
+        Integrator::VelocityVerlet => {
+            // Medio impulso, desplazamiento completo y otro medio impulso con
+            // la gravedad en la posición nueva
+            let acceleration = accelerations(physics, max_acceleration, &bodies(asteroids));
+            for (asteroid, a) in asteroids.iter_mut().zip(acceleration) {
+                asteroid.vel.0 += a.0 * dt / 2.0;
+                asteroid.vel.1 += a.1 * dt / 2.0;
+                asteroid.pos.0 += asteroid.vel.0 * dt;
+                asteroid.pos.1 += asteroid.vel.1 * dt;
+            }
+            let acceleration = accelerations(physics, max_acceleration, &bodies(asteroids));
+            for (asteroid, a) in asteroids.iter_mut().zip(acceleration) {
+                asteroid.vel.0 += a.0 * dt / 2.0;
+                asteroid.vel.1 += a.1 * dt / 2.0;
+            }
+        }
+        Integrator::Rk4 => {
+            // Cada etapa k es (velocidad, aceleración) en un estado de prueba
+            let v1: Vec<(f32, f32)> = asteroids.iter().map(|asteroid| asteroid.vel).collect();
+            let a1 = accelerations(physics, max_acceleration, &bodies(asteroids));
+            let trial_velocity = |a: &[(f32, f32)], scale: f32| -> Vec<(f32, f32)> {
+                v1.iter().zip(a).map(|(v, a)| (v.0 + a.0 * scale, v.1 + a.1 * scale)).collect()
+            };
+            let v2 = trial_velocity(&a1, dt / 2.0);
+            let a2 = accelerations(physics, max_acceleration, &shifted(asteroids, &v1, dt / 2.0));
+            let v3 = trial_velocity(&a2, dt / 2.0);
+            let a3 = accelerations(physics, max_acceleration, &shifted(asteroids, &v2, dt / 2.0));
+            let v4 = trial_velocity(&a3, dt);
+            let a4 = accelerations(physics, max_acceleration, &shifted(asteroids, &v3, dt));
+
+            for (i, asteroid) in asteroids.iter_mut().enumerate() {
+                asteroid.pos.0 += dt / 6.0 * (v1[i].0 + 2.0 * v2[i].0 + 2.0 * v3[i].0 + v4[i].0);
+                asteroid.pos.1 += dt / 6.0 * (v1[i].1 + 2.0 * v2[i].1 + 2.0 * v3[i].1 + v4[i].1);
+                asteroid.vel.0 += dt / 6.0 * (a1[i].0 + 2.0 * a2[i].0 + 2.0 * a3[i].0 + a4[i].0);
+                asteroid.vel.1 += dt / 6.0 * (a1[i].1 + 2.0 * a2[i].1 + 2.0 * a3[i].1 + a4[i].1);
+            }
+        }
+    }
+
+    for asteroid in asteroids {
+        asteroid.pos = wrap_position(asteroid.pos);
+    }
+}
@@ -0,0 +1,41 @@
+// Simulación de Bubbleroid sin dependencias de ggez: se puede ejecutar en
+// CI, bots o herramientas sin ventana ni GPU. El front-end de `gui/`
+// solo dibuja el `World` y le pasa la entrada del jugador.
+
+mod accretion;
+mod config;
+pub mod constants;
+mod diagnostics;
+mod entities;
+mod fragment;
+mod geometry;
+mod grid;
+mod highscores;
+mod integrator;
+mod physics;
+mod replay;
+mod savegame;
+mod serde_text;
+mod timestep;
+mod world;
+
+pub use accretion::merge;
+pub use config::{
+    AsteroidConfig, BulletConfig, Config, ConfigError, ConfigWatcher, GravitySolver, Integrator, ParticleConfig, PhysicsConfig,
+    PlayerConfig,
+};
+pub use diagnostics::Diagnostics;
+pub use entities::{Asteroid, Bullet, Particle, Rgba, ThrusterParticle, Vertex};
+pub use fragment::fragment;
+pub use geometry::{gyration_squared, point_in_polygon, polygons_intersect, rotate, support};
+pub use grid::SpatialGrid;
+pub use highscores::{HighScore, HighScoreError, HighScores, HIGH_SCORE_COUNT, NAME_LENGTH};
+pub use integrator::integrate;
+pub use physics::{
+    barnes_hut_gravity, calculate_gravity, escape_speed, exact_gravity, gravity_forces, potential_energy, wrap_position,
+    wrapped_delta, wrapped_distance, Body,
+};
+pub use replay::{ConfigChange, Recorder, Replay, ReplayError, ReplayEvent, ReplayInputs, REPLAY_VERSION};
+pub use savegame::{SaveError, SaveGame, SAVE_VERSION};
+pub use timestep::{interpolate_position, FixedTimestep};
+pub use world::{Input, World};
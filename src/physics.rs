@@ -0,0 +1,317 @@
+use crate::config::{GravitySolver, PhysicsConfig};
+use crate::constants::{WINDOW_HEIGHT, WINDOW_WIDTH};
+use crate::entities::Asteroid;
+
+// Vector más corto de `from` a `to` en el mundo toroidal (convenio de la
+// imagen mínima): dos puntos a lados opuestos de un borde están cerca
+pub fn wrapped_delta(from: (f32, f32), to: (f32, f32)) -> (f32, f32) {
+    (shortest(to.0 - from.0, WINDOW_WIDTH), shortest(to.1 - from.1, WINDOW_HEIGHT))
+}
+
+// Las posiciones están dentro de la pantalla, así que basta con una vuelta
+fn shortest(delta: f32, length: f32) -> f32 {
+    if delta > length / 2.0 {
+        delta - length
+    } else if delta < -length / 2.0 {
+        delta + length
+    } else {
+        delta
+    }
+}
+
+pub fn wrapped_distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    let (dx, dy) = wrapped_delta(a, b);
+    (dx * dx + dy * dy).sqrt()
+}
+
+// Devuelve la posición al interior de la pantalla
+pub fn wrap_position(pos: (f32, f32)) -> (f32, f32) {
+    (pos.0.rem_euclid(WINDOW_WIDTH), pos.1.rem_euclid(WINDOW_HEIGHT))
+}
+
+// Reduce el módulo de `v` a `max` como mucho, sin cambiar su dirección
+pub(crate) fn clamp_magnitude(v: (f32, f32), max: f32) -> (f32, f32) {
+    let magnitude = (v.0 * v.0 + v.1 * v.1).sqrt();
+    let limit = if magnitude > max { max / magnitude } else { 1.0 };
+    (v.0 * limit, v.1 * limit)
+}
+
+// Lo que la gravedad necesita de cada cuerpo. Además de los asteroides, los
+// integradores usan parejas (posición, masa) para los estados intermedios.
+pub trait Body {
+    fn pos(&self) -> (f32, f32);
+    fn mass(&self) -> f32;
+}
+
+impl Body for Asteroid {
+    fn pos(&self) -> (f32, f32) {
+        self.pos
+    }
+
+    fn mass(&self) -> f32 {
+        self.mass
+    }
+}
+
+impl Body for ((f32, f32), f32) {
+    fn pos(&self) -> (f32, f32) {
+        self.0
+    }
+
+    fn mass(&self) -> f32 {
+        self.1
+    }
+}
+
+// Fuerza sobre un cuerpo de masa `mass1` por otro de masa `mass2` situado a
+// `delta` de él. Con el suavizado de Plummer la fuerza va como
+// r / (r² + ε²)^(3/2): igual que la de Newton lejos, y en vez de dispararse al
+// acercarse cae a cero en el centro, así que nunca es infinita.
+pub fn calculate_gravity(physics: &PhysicsConfig, mass1: f32, mass2: f32, delta: (f32, f32)) -> (f32, f32) {
+    let softened = delta.0 * delta.0 + delta.1 * delta.1 + physics.softening * physics.softening;
+    let strength = physics.gravity_constant * mass1 * mass2 * physics.gravity_scale / (softened * softened.sqrt());
+    if !strength.is_finite() {
+        return (0.0, 0.0);
+    }
+    (strength * delta.0, strength * delta.1)
+}
+
+// Fuerza de gravedad sobre cada asteroide con el método elegido en la
+// configuración. Con pocos asteroides siempre se usa el cálculo exacto.
+pub fn gravity_forces<B: Body>(physics: &PhysicsConfig, asteroids: &[B]) -> Vec<(f32, f32)> {
+    match physics.gravity_solver {
+        GravitySolver::BarnesHut if asteroids.len() >= physics.barnes_hut_threshold => {
+            barnes_hut_gravity(physics, asteroids, physics.barnes_hut_theta)
+        }
+        _ => exact_gravity(physics, asteroids),
+    }
+}
+
+// Suma exacta de todas las parejas: O(n²)
+pub fn exact_gravity<B: Body>(physics: &PhysicsConfig, asteroids: &[B]) -> Vec<(f32, f32)> {
+    let asteroid_count = asteroids.len();
+    let mut gravity_forces = vec![(0.0, 0.0); asteroid_count];
+
+    for i in 0..asteroid_count {
+        for j in (i + 1)..asteroid_count {
+            let delta = wrapped_delta(asteroids[i].pos(), asteroids[j].pos());
+            let (force_x, force_y) = calculate_gravity(physics, asteroids[i].mass(), asteroids[j].mass(), delta);
+
+            gravity_forces[i].0 += force_x;
+            gravity_forces[i].1 += force_y;
+            gravity_forces[j].0 -= force_x;
+            gravity_forces[j].1 -= force_y;
+        }
+    }
+
+    gravity_forces
+}
+
+// Energía potencial de la gravedad suavizada entre todas las parejas. Es la
+// que corresponde a `calculate_gravity`: -G·m1·m2 / √(r² + ε²).
+pub fn potential_energy<B: Body>(physics: &PhysicsConfig, asteroids: &[B]) -> f64 {
+    let strength = physics.gravity_constant as f64 * physics.gravity_scale as f64;
+    let softening = physics.softening as f64 * physics.softening as f64;
+    let mut energy = 0.0;
+    for i in 0..asteroids.len() {
+        for j in (i + 1)..asteroids.len() {
+            let (dx, dy) = wrapped_delta(asteroids[i].pos(), asteroids[j].pos());
+            let distance = (dx as f64 * dx as f64 + dy as f64 * dy as f64 + softening).sqrt();
+            energy -= strength * asteroids[i].mass() as f64 * asteroids[j].mass() as f64 / distance;
+        }
+    }
+    energy
+}
+
+// Velocidad relativa por debajo de la cual dos cuerpos a `delta` uno del otro
+// quedan ligados por su gravedad. Los asteroides aceleran con la fuerza tal
+// cual (masa inercial 1), así que la energía cinética del movimiento relativo
+// es ¼·v², frente a la potencial de `potential_energy`.
+pub fn escape_speed(physics: &PhysicsConfig, mass1: f32, mass2: f32, delta: (f32, f32)) -> f32 {
+    let distance = (delta.0 * delta.0 + delta.1 * delta.1 + physics.softening * physics.softening).sqrt();
+    2.0 * (physics.gravity_constant * physics.gravity_scale * mass1 * mass2 / distance).sqrt()
+}
+
+// Profundidad máxima del árbol: por debajo, los asteroides que caen en el
+// mismo nodo (casi en la misma posición) comparten hoja
+const MAX_DEPTH: u32 = 24;
+const NO_NODE: usize = usize::MAX;
+
+struct Node {
+    center: (f32, f32),
+    half: f32,
+    mass: f32,
+    // Suma de masa por posición; al terminar de construir es el centro de masas
+    mass_pos: (f32, f32),
+    children: [usize; 4],
+    // Primer asteroide de la hoja; los demás siguen en `next_in_leaf`
+    first: usize,
+    is_leaf: bool,
+}
+
+impl Node {
+    fn new(center: (f32, f32), half: f32) -> Node {
+        Node {
+            center,
+            half,
+            mass: 0.0,
+            mass_pos: (0.0, 0.0),
+            children: [NO_NODE; 4],
+            first: NO_NODE,
+            is_leaf: true,
+        }
+    }
+
+    fn contains(&self, pos: (f32, f32)) -> bool {
+        (pos.0 - self.center.0).abs() <= self.half && (pos.1 - self.center.1).abs() <= self.half
+    }
+
+    fn quadrant(&self, pos: (f32, f32)) -> usize {
+        (pos.0 >= self.center.0) as usize | ((pos.1 >= self.center.1) as usize) << 1
+    }
+}
+
+// Árbol cuaternario con la masa y el centro de masas de cada nodo
+struct QuadTree {
+    nodes: Vec<Node>,
+    next_in_leaf: Vec<usize>,
+}
+
+impl QuadTree {
+    fn build<B: Body>(asteroids: &[B]) -> QuadTree {
+        let (mut min, mut max) = ((f32::INFINITY, f32::INFINITY), (f32::NEG_INFINITY, f32::NEG_INFINITY));
+        for asteroid in asteroids {
+            let pos = asteroid.pos();
+            min = (min.0.min(pos.0), min.1.min(pos.1));
+            max = (max.0.max(pos.0), max.1.max(pos.1));
+        }
+        let center = ((min.0 + max.0) / 2.0, (min.1 + max.1) / 2.0);
+        let half = ((max.0 - min.0).max(max.1 - min.1) / 2.0).max(1.0);
+
+        let mut tree = QuadTree {
+            nodes: vec![Node::new(center, half)],
+            next_in_leaf: vec![NO_NODE; asteroids.len()],
+        };
+        for (index, asteroid) in asteroids.iter().enumerate() {
+            tree.insert(asteroids, index, asteroid);
+        }
+        for node in &mut tree.nodes {
+            if node.mass > 0.0 {
+                node.mass_pos = (node.mass_pos.0 / node.mass, node.mass_pos.1 / node.mass);
+            }
+        }
+        tree
+    }
+
+    fn insert<B: Body>(&mut self, asteroids: &[B], index: usize, asteroid: &B) {
+        let mut node = 0;
+        let mut depth = 0;
+        loop {
+            self.add_mass(node, asteroid);
+
+            if !self.nodes[node].is_leaf {
+                node = self.child(node, asteroid.pos());
+                depth += 1;
+                continue;
+            }
+            if self.nodes[node].first == NO_NODE || depth >= MAX_DEPTH {
+                self.next_in_leaf[index] = self.nodes[node].first;
+                self.nodes[node].first = index;
+                return;
+            }
+
+            // La hoja ya tenía un asteroide: se divide y se baja el que había
+            let existing = self.nodes[node].first;
+            self.nodes[node].first = NO_NODE;
+            self.nodes[node].is_leaf = false;
+            let child = self.child(node, asteroids[existing].pos());
+            self.add_mass(child, &asteroids[existing]);
+            self.nodes[child].first = existing;
+
+            node = self.child(node, asteroid.pos());
+            depth += 1;
+        }
+    }
+
+    fn add_mass<B: Body>(&mut self, node: usize, asteroid: &B) {
+        let (mass, pos) = (asteroid.mass(), asteroid.pos());
+        let node = &mut self.nodes[node];
+        node.mass += mass;
+        node.mass_pos.0 += mass * pos.0;
+        node.mass_pos.1 += mass * pos.1;
+    }
+
+    // Hijo del cuadrante de `pos`, creándolo si hace falta
+    fn child(&mut self, node: usize, pos: (f32, f32)) -> usize {
+        let quadrant = self.nodes[node].quadrant(pos);
+        if self.nodes[node].children[quadrant] == NO_NODE {
+            let parent = &self.nodes[node];
+            let half = parent.half / 2.0;
+            let center = (
+                parent.center.0 + if quadrant & 1 != 0 { half } else { -half },
+                parent.center.1 + if quadrant & 2 != 0 { half } else { -half },
+            );
+            self.nodes.push(Node::new(center, half));
+            let child = self.nodes.len() - 1;
+            self.nodes[node].children[quadrant] = child;
+        }
+        self.nodes[node].children[quadrant]
+    }
+}
+
+fn add_attraction(physics: &PhysicsConfig, force: &mut (f32, f32), from: (f32, f32), mass: f32, to: (f32, f32), other_mass: f32) {
+    let (force_x, force_y) = calculate_gravity(physics, mass, other_mass, wrapped_delta(from, to));
+    force.0 += force_x;
+    force.1 += force_y;
+}
+
+// Aproximación de Barnes–Hut: O(n log n). Un nodo lejano se trata como una
+// sola masa en su centro de masas cuando su tamaño dividido entre la distancia
+// es menor que `theta`; con theta = 0 el resultado es el exacto. Las
+// distancias dan la vuelta por los bordes igual que en el cálculo exacto.
+pub fn barnes_hut_gravity<B: Body>(physics: &PhysicsConfig, asteroids: &[B], theta: f32) -> Vec<(f32, f32)> {
+    if asteroids.is_empty() {
+        return Vec::new();
+    }
+    let tree = QuadTree::build(asteroids);
+    let mut stack = Vec::new();
+
+    asteroids
+        .iter()
+        .enumerate()
+        .map(|(index, asteroid)| {
+            let (pos, mass) = (asteroid.pos(), asteroid.mass());
+            let mut force = (0.0, 0.0);
+            stack.clear();
+            stack.push(0);
+            while let Some(node) = stack.pop() {
+                let node = &tree.nodes[node];
+                if node.is_leaf {
+                    let mut other = node.first;
+                    while other != NO_NODE {
+                        if other != index {
+                            let body = &asteroids[other];
+                            add_attraction(physics, &mut force, pos, mass, body.pos(), body.mass());
+                        }
+                        other = tree.next_in_leaf[other];
+                    }
+                    continue;
+                }
+
+                let distance = wrapped_distance(pos, node.mass_pos);
+                // Un nodo que contiene al propio asteroide siempre se abre. Para
+                // tratarlo como una sola masa, además, el nodo entero tiene que
+                // quedar a un mismo lado de la línea donde cambia la imagen más
+                // cercana; si no, unos asteroides tirarían por un lado y otros por otro.
+                let (cx, cy) = wrapped_delta(pos, node.center);
+                let same_image = cx.abs() + node.half <= WINDOW_WIDTH / 2.0 && cy.abs() + node.half <= WINDOW_HEIGHT / 2.0;
+                if !node.contains(pos) && 2.0 * node.half < theta * distance && same_image {
+                    add_attraction(physics, &mut force, pos, mass, node.mass_pos, node.mass);
+                } else {
+                    stack.extend(node.children.iter().copied().filter(|&child| child != NO_NODE));
+                }
+            }
+            force
+        })
+        .collect()
+}
@@ -0,0 +1,371 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::config::{Config, ConfigError};
+use crate::constants::TICK_DT;
+use crate::world::{Input, World};
+
+// Formato de fichero:
+//   "BBRP" | versión u16 | semilla u64 | ticks totales u64
+//   | configuración | nº eventos u32
+//   y por cada evento: delta de tick (varint) | botones (u8)
+//   | nº recargas de configuración u32
+//   y por cada recarga: tick u64 | configuración
+// Cada configuración va como longitud u32 seguida del texto TOML.
+// Todos los enteros fijos van en little-endian. Un evento marca un cambio en
+// los controles, que se mantienen igual hasta el siguiente evento.
+const REPLAY_MAGIC: &[u8; 4] = b"BBRP";
+// Una repetición solo es válida con la misma simulación: hay que incrementar
+// la versión con cada cambio en las reglas o la física del juego.
+pub const REPLAY_VERSION: u16 = 20;
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    NotAReplay,
+    UnsupportedVersion(u16),
+    Corrupt(&'static str),
+    Config(ConfigError),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::Io(err) => write!(f, "error de lectura/escritura: {}", err),
+            ReplayError::NotAReplay => write!(f, "el fichero no es una repetición de Bubbleroid"),
+            ReplayError::UnsupportedVersion(version) => write!(
+                f,
+                "repetición de la versión {} no compatible (esta build usa la versión {})",
+                version, REPLAY_VERSION
+            ),
+            ReplayError::Corrupt(reason) => write!(f, "repetición corrupta: {}", reason),
+            ReplayError::Config(err) => write!(f, "configuración de la repetición no válida: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<io::Error> for ReplayError {
+    fn from(err: io::Error) -> Self {
+        if err.kind() == io::ErrorKind::UnexpectedEof {
+            ReplayError::Corrupt("fichero truncado")
+        } else {
+            ReplayError::Io(err)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReplayEvent {
+    pub tick: u64,
+    pub input: Input,
+}
+
+// Configuración recargada en caliente durante la partida
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigChange {
+    pub tick: u64,
+    pub config: Config,
+}
+
+// Partida grabada: la semilla y configuración iniciales más cada cambio en los
+// controles y cada recarga de la configuración
+#[derive(Clone, Debug, PartialEq)]
+pub struct Replay {
+    pub seed: u64,
+    pub ticks: u64,
+    pub config: Config,
+    pub events: Vec<ReplayEvent>,
+    pub config_changes: Vec<ConfigChange>,
+}
+
+fn input_to_bits(input: &Input) -> u8 {
+    (input.thrust as u8)
+        | (input.rotate_left as u8) << 1
+        | (input.rotate_right as u8) << 2
+        | (input.fire as u8) << 3
+}
+
+fn input_from_bits(bits: u8) -> Result<Input, ReplayError> {
+    if bits >> 4 != 0 {
+        return Err(ReplayError::Corrupt("botones desconocidos"));
+    }
+    Ok(Input {
+        thrust: bits & 1 != 0,
+        rotate_left: bits & (1 << 1) != 0,
+        rotate_right: bits & (1 << 2) != 0,
+        fire: bits & (1 << 3) != 0,
+    })
+}
+
+fn write_varint(w: &mut impl Write, mut value: u64) -> io::Result<()> {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            return w.write_all(&[byte]);
+        }
+        w.write_all(&[byte | 0x80])?;
+    }
+}
+
+fn read_varint(r: &mut impl Read) -> Result<u64, ReplayError> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0u8; 1];
+        r.read_exact(&mut byte)?;
+        value |= ((byte[0] & 0x7f) as u64) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(ReplayError::Corrupt("entero demasiado largo"))
+}
+
+pub(crate) fn read_u16(r: &mut impl Read) -> io::Result<u16> {
+    let mut buf = [0u8; 2];
+    r.read_exact(&mut buf)?;
+    Ok(u16::from_le_bytes(buf))
+}
+
+pub(crate) fn read_u32(r: &mut impl Read) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64(r: &mut impl Read) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn write_config(w: &mut impl Write, config: &Config) -> io::Result<()> {
+    let text = config.to_toml();
+    w.write_all(&(text.len() as u32).to_le_bytes())?;
+    w.write_all(text.as_bytes())
+}
+
+fn read_config(r: &mut impl Read) -> Result<Config, ReplayError> {
+    let len = read_u32(r)? as u64;
+    let mut text = String::new();
+    r.take(len)
+        .read_to_string(&mut text)
+        .map_err(|_| ReplayError::Corrupt("configuración ilegible"))?;
+    if text.len() as u64 != len {
+        return Err(ReplayError::Corrupt("fichero truncado"));
+    }
+    Config::from_toml(&text).map_err(ReplayError::Config)
+}
+
+impl Replay {
+    pub fn write_to(&self, w: &mut impl Write) -> io::Result<()> {
+        w.write_all(REPLAY_MAGIC)?;
+        w.write_all(&REPLAY_VERSION.to_le_bytes())?;
+        w.write_all(&self.seed.to_le_bytes())?;
+        w.write_all(&self.ticks.to_le_bytes())?;
+        write_config(w, &self.config)?;
+        w.write_all(&(self.events.len() as u32).to_le_bytes())?;
+
+        let mut last_tick = 0;
+        for event in &self.events {
+            write_varint(w, event.tick - last_tick)?;
+            w.write_all(&[input_to_bits(&event.input)])?;
+            last_tick = event.tick;
+        }
+
+        w.write_all(&(self.config_changes.len() as u32).to_le_bytes())?;
+        for change in &self.config_changes {
+            w.write_all(&change.tick.to_le_bytes())?;
+            write_config(w, &change.config)?;
+        }
+        Ok(())
+    }
+
+    pub fn read_from(r: &mut impl Read) -> Result<Replay, ReplayError> {
+        let mut magic = [0u8; 4];
+        r.read_exact(&mut magic).map_err(|_| ReplayError::NotAReplay)?;
+        if &magic != REPLAY_MAGIC {
+            return Err(ReplayError::NotAReplay);
+        }
+        let version = read_u16(r)?;
+        if version != REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion(version));
+        }
+
+        let seed = read_u64(r)?;
+        let ticks = read_u64(r)?;
+        let config = read_config(r)?;
+        let count = read_u32(r)?;
+
+        let mut events = Vec::new();
+        let mut tick = 0u64;
+        for index in 0..count {
+            let delta = read_varint(r)?;
+            // Solo se consume un evento por tick: dos en el mismo tick
+            // atascarían todos los siguientes
+            if delta == 0 && index > 0 {
+                return Err(ReplayError::Corrupt("dos eventos en el mismo tick"));
+            }
+            tick = tick.checked_add(delta).ok_or(ReplayError::Corrupt("tick fuera de rango"))?;
+            if tick >= ticks {
+                return Err(ReplayError::Corrupt("evento posterior al final"));
+            }
+            let mut bits = [0u8; 1];
+            r.read_exact(&mut bits)?;
+            events.push(ReplayEvent { tick, input: input_from_bits(bits[0])? });
+        }
+
+        let count = read_u32(r)?;
+        let mut config_changes = Vec::new();
+        let mut last_tick = 0;
+        for _ in 0..count {
+            let tick = read_u64(r)?;
+            if tick < last_tick || tick >= ticks {
+                return Err(ReplayError::Corrupt("recarga de configuración fuera de orden"));
+            }
+            config_changes.push(ConfigChange { tick, config: read_config(r)? });
+            last_tick = tick;
+        }
+
+        Ok(Replay { seed, ticks, config, events, config_changes })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Replay, ReplayError> {
+        let mut reader = BufReader::new(File::open(path).map_err(ReplayError::Io)?);
+        Replay::read_from(&mut reader)
+    }
+
+    // Entrada de cada tick, de 0 a `ticks`, lista para pasar a `World::step`,
+    // junto con la configuración que haya que aplicar antes de ese tick
+    pub fn into_inputs(self) -> ReplayInputs {
+        ReplayInputs {
+            replay: self,
+            tick: 0,
+            next_event: 0,
+            next_config_change: 0,
+            current: Input::default(),
+        }
+    }
+
+    // Reproduce la partida completa sin ventana y devuelve el estado final
+    pub fn play(self) -> World {
+        self.play_with(|_| {})
+    }
+
+    // Como `play`, pero llamando a `on_tick` con el estado tras cada paso
+    pub fn play_with(self, mut on_tick: impl FnMut(&World)) -> World {
+        let mut world = World::new(self.seed, self.config.clone());
+        for (input, config) in self.into_inputs() {
+            if let Some(config) = config {
+                world.config = config;
+            }
+            world.step(TICK_DT, &input);
+            on_tick(&world);
+        }
+        world
+    }
+}
+
+pub struct ReplayInputs {
+    replay: Replay,
+    tick: u64,
+    next_event: usize,
+    next_config_change: usize,
+    current: Input,
+}
+
+impl Iterator for ReplayInputs {
+    type Item = (Input, Option<Config>);
+
+    fn next(&mut self) -> Option<(Input, Option<Config>)> {
+        if self.tick >= self.replay.ticks {
+            return None;
+        }
+
+        if let Some(event) = self.replay.events.get(self.next_event) {
+            if event.tick == self.tick {
+                self.current = event.input;
+                self.next_event += 1;
+            }
+        }
+        // Si hubo varias recargas en el mismo tick, vale la última
+        let mut config = None;
+        while let Some(change) = self.replay.config_changes.get(self.next_config_change) {
+            if change.tick != self.tick {
+                break;
+            }
+            config = Some(change.config.clone());
+            self.next_config_change += 1;
+        }
+
+        self.tick += 1;
+        Some((self.current, config))
+    }
+}
+
+// Graba la entrada que recibe la simulación, tick a tick
+pub struct Recorder {
+    seed: u64,
+    config: Config,
+    ticks: u64,
+    events: Vec<ReplayEvent>,
+    config_changes: Vec<ConfigChange>,
+    last: Input,
+}
+
+impl Recorder {
+    pub fn new(seed: u64, config: Config) -> Self {
+        Recorder {
+            seed,
+            config,
+            ticks: 0,
+            events: Vec::new(),
+            config_changes: Vec::new(),
+            last: Input::default(),
+        }
+    }
+
+    // Sigue grabando a partir de una repetición a medias, la de una partida guardada
+    pub fn resume(replay: Replay) -> Self {
+        Recorder {
+            seed: replay.seed,
+            config: replay.config,
+            ticks: replay.ticks,
+            last: replay.events.last().map_or(Input::default(), |event| event.input),
+            events: replay.events,
+            config_changes: replay.config_changes,
+        }
+    }
+
+    pub fn record_config(&mut self, tick: u64, config: &Config) {
+        self.config_changes.push(ConfigChange { tick, config: config.clone() });
+    }
+
+    pub fn record(&mut self, tick: u64, input: &Input) {
+        if *input != self.last {
+            self.events.push(ReplayEvent { tick, input: *input });
+            self.last = *input;
+        }
+        self.ticks = tick + 1;
+    }
+
+    pub fn finish(&self) -> Replay {
+        Replay {
+            seed: self.seed,
+            ticks: self.ticks,
+            config: self.config.clone(),
+            events: self.events.clone(),
+            config_changes: self.config_changes.clone(),
+        }
+    }
+}
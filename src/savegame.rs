@@ -0,0 +1,114 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::replay::{read_u16, read_u32, Replay, ReplayError};
+use crate::world::World;
+
+// Formato de fichero:
+//   "BBSV" | versión u16 | repetición grabada hasta ahora (formato de replay.rs)
+//   | longitud u32 | estado del `World` en TOML
+// La repetición permite seguir grabando la partida al continuarla, de modo que
+// la repetición final cubre la partida entera.
+const SAVE_MAGIC: &[u8; 4] = b"BBSV";
+// Hay que incrementarla con cada cambio en los campos del `World` o de las entidades
+pub const SAVE_VERSION: u16 = 4;
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    NotASave,
+    UnsupportedVersion(u16),
+    Corrupt(&'static str),
+    Replay(ReplayError),
+    State(toml::de::Error),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "error de lectura/escritura: {}", err),
+            SaveError::NotASave => write!(f, "el fichero no es una partida guardada de Bubbleroid"),
+            SaveError::UnsupportedVersion(version) => write!(
+                f,
+                "partida guardada con la versión {} no compatible (esta build usa la versión {})",
+                version, SAVE_VERSION
+            ),
+            SaveError::Corrupt(reason) => write!(f, "partida guardada corrupta: {}", reason),
+            SaveError::Replay(err) => write!(f, "repetición de la partida guardada no válida: {}", err),
+            SaveError::State(err) => write!(f, "estado de la partida guardada no válido: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<io::Error> for SaveError {
+    fn from(err: io::Error) -> Self {
+        if err.kind() == io::ErrorKind::UnexpectedEof {
+            SaveError::Corrupt("fichero truncado")
+        } else {
+            SaveError::Io(err)
+        }
+    }
+}
+
+// Partida a medias: el estado completo de la simulación y su grabación
+pub struct SaveGame {
+    pub world: World,
+    pub replay: Replay,
+}
+
+impl SaveGame {
+    pub fn write_to(&self, w: &mut impl Write) -> io::Result<()> {
+        w.write_all(SAVE_MAGIC)?;
+        w.write_all(&SAVE_VERSION.to_le_bytes())?;
+        self.replay.write_to(w)?;
+
+        let state = toml::to_string(&self.world).expect("el estado siempre se puede serializar");
+        w.write_all(&(state.len() as u32).to_le_bytes())?;
+        w.write_all(state.as_bytes())
+    }
+
+    pub fn read_from(r: &mut impl Read) -> Result<SaveGame, SaveError> {
+        let mut magic = [0u8; 4];
+        r.read_exact(&mut magic).map_err(|_| SaveError::NotASave)?;
+        if &magic != SAVE_MAGIC {
+            return Err(SaveError::NotASave);
+        }
+        let version = read_u16(r)?;
+        if version != SAVE_VERSION {
+            return Err(SaveError::UnsupportedVersion(version));
+        }
+
+        let replay = Replay::read_from(r).map_err(SaveError::Replay)?;
+
+        let len = read_u32(r)? as u64;
+        let mut state = String::new();
+        r.take(len)
+            .read_to_string(&mut state)
+            .map_err(|_| SaveError::Corrupt("estado ilegible"))?;
+        if state.len() as u64 != len {
+            return Err(SaveError::Corrupt("fichero truncado"));
+        }
+        let world: World = toml::from_str(&state).map_err(SaveError::State)?;
+        world.config.validate().map_err(|_| SaveError::Corrupt("configuración no válida"))?;
+
+        if replay.ticks != world.tick || replay.seed != world.seed {
+            return Err(SaveError::Corrupt("la repetición no corresponde a la partida"));
+        }
+        Ok(SaveGame { world, replay })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<SaveGame, SaveError> {
+        let mut reader = BufReader::new(File::open(path).map_err(SaveError::Io)?);
+        SaveGame::read_from(&mut reader)
+    }
+}
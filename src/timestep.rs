@@ -0,0 +1,100 @@
+use crate::constants::*;
+use crate::physics::{wrap_position, wrapped_delta};
+
+// Acumulador para avanzar la simulación a paso fijo, independiente de los fps
+pub struct FixedTimestep {
+    accumulator: f32,
+    dt: f32,
+}
+
+impl Default for FixedTimestep {
+    fn default() -> Self {
+        Self::new(TICK_DT)
+    }
+}
+
+impl FixedTimestep {
+    pub fn new(dt: f32) -> Self {
+        FixedTimestep { accumulator: 0.0, dt }
+    }
+
+    pub fn dt(&self) -> f32 {
+        self.dt
+    }
+
+    // Devuelve cuántos pasos fijos hay que simular para este frame.
+    // Los frames muy largos se recortan para no entrar en una espiral de pasos.
+    pub fn advance(&mut self, frame_time: f32) -> u32 {
+        self.accumulator += frame_time.clamp(0.0, MAX_FRAME_TIME);
+
+        let mut ticks = 0;
+        while self.accumulator >= self.dt {
+            self.accumulator -= self.dt;
+            ticks += 1;
+        }
+        ticks
+    }
+
+    // Fracción del siguiente paso ya transcurrida, para interpolar el dibujo
+    pub fn alpha(&self) -> f32 {
+        self.accumulator / self.dt
+    }
+}
+
+// Interpola entre dos posiciones teniendo en cuenta que el mundo da la vuelta
+// en los bordes: un objeto que cruza el borde no se dibuja atravesando la pantalla.
+pub fn interpolate_position(prev: (f32, f32), pos: (f32, f32), alpha: f32) -> (f32, f32) {
+    let (dx, dy) = wrapped_delta(prev, pos);
+    wrap_position((prev.0 + dx * alpha, prev.1 + dy * alpha))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Ticks de cada frame durante un segundo a `fps` frames por segundo
+    fn ticks_per_frame(fps: u32) -> Vec<u32> {
+        let mut timestep = FixedTimestep::default();
+        (0..fps).map(|_| timestep.advance(1.0 / fps as f32)).collect()
+    }
+
+    #[test]
+    fn thirty_fps_runs_four_ticks_per_frame() {
+        let ticks = ticks_per_frame(30);
+        assert!(ticks.iter().all(|&n| (3..=5).contains(&n)), "{:?}", ticks);
+        let total: u32 = ticks.iter().sum();
+        assert!(total.abs_diff(TICK_RATE as u32) <= 1, "{} ticks en un segundo", total);
+    }
+
+    #[test]
+    fn high_fps_runs_at_most_one_tick_per_frame() {
+        let ticks = ticks_per_frame(144);
+        assert!(ticks.iter().all(|&n| n <= 1), "{:?}", ticks);
+        assert!(ticks.contains(&0));
+        let total: u32 = ticks.iter().sum();
+        assert!(total.abs_diff(TICK_RATE as u32) <= 1, "{} ticks en un segundo", total);
+    }
+
+    #[test]
+    fn long_frame_is_clamped() {
+        let mut timestep = FixedTimestep::default();
+        // Tras un parón de diez segundos solo se recupera MAX_FRAME_TIME
+        let ticks = timestep.advance(10.0);
+        let expected = (MAX_FRAME_TIME / TICK_DT).round() as u32;
+        assert!(ticks.abs_diff(expected) <= 1, "{} ticks tras el parón", ticks);
+        assert_eq!(timestep.advance(0.0), 0, "han quedado ticks pendientes");
+        assert_eq!(timestep.advance(-1.0), 0);
+    }
+
+    #[test]
+    fn alpha_stays_below_one() {
+        let mut timestep = FixedTimestep::default();
+        for frame in 0..1000 {
+            // Frames de duración variable, algunos más cortos que un tick
+            let frame_time = (frame % 7) as f32 * 0.004 + (frame % 3) as f32 * 0.0011;
+            timestep.advance(frame_time);
+            let alpha = timestep.alpha();
+            assert!((0.0..1.0).contains(&alpha), "alpha {} tras el frame {}", alpha, frame);
+        }
+    }
+}
@@ -0,0 +1,708 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::accretion::merge;
+use crate::config::Config;
+use crate::constants::*;
+use crate::diagnostics::Diagnostics;
+use crate::entities::{Asteroid, Bullet, Particle, Rgba, ThrusterParticle};
+use crate::fragment::fragment;
+use crate::geometry::rotate;
+use crate::grid::SpatialGrid;
+use crate::integrator::integrate;
+use crate::physics::{calculate_gravity, clamp_magnitude, escape_speed, wrap_position, wrapped_delta, wrapped_distance};
+
+// Estado de los controles durante un paso. Empuje y giro son teclas mantenidas;
+// el disparo es una pulsación que el front-end envía en un solo paso. Empezar
+// otra partida es cosa del front-end, que crea un `World` nuevo.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Input {
+    pub thrust: bool,
+    pub rotate_left: bool,
+    pub rotate_right: bool,
+    pub fire: bool,
+}
+
+// Estado completo de la simulación, sin dependencias del motor gráfico. Se
+// puede serializar entero, generador aleatorio incluido, para guardar la partida.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct World {
+    pub player_pos: (f32, f32),
+    pub player_prev_pos: (f32, f32),
+    pub player_vel: (f32, f32),
+    pub player_angle: f32,
+    pub bullets: Vec<Bullet>,
+    pub asteroids: Vec<Asteroid>,
+    pub is_game_over: bool,
+    pub destroyed_count: f32,
+    pub particles: Vec<Particle>,
+    pub level: u32,
+    pub score: u32,
+    pub lives: u32,
+    pub next_extra_life: u32,
+    pub invulnerable_time: f32,
+    // Segundos que faltan para poder volver a disparar
+    pub fire_cooldown: f32,
+    pub thruster_particles: Vec<ThrusterParticle>,
+    #[serde(with = "crate::serde_text")]
+    pub seed: u64,
+    pub tick: u64,
+    pub config: Config,
+    #[serde(with = "rng_state")]
+    rng: ChaCha8Rng,
+    // Fase amplia de colisiones; se reconstruye cada tick
+    #[serde(skip)]
+    grid: SpatialGrid,
+}
+
+// El generador se guarda como su semilla, su flujo y la posición dentro de
+// él, que bastan para reconstruirlo exactamente
+mod rng_state {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct RngState {
+        seed: [u8; 32],
+        #[serde(with = "crate::serde_text")]
+        stream: u64,
+        #[serde(with = "crate::serde_text")]
+        word_pos: u128,
+    }
+
+    pub fn serialize<S: Serializer>(rng: &ChaCha8Rng, serializer: S) -> Result<S::Ok, S::Error> {
+        RngState {
+            seed: rng.get_seed(),
+            stream: rng.get_stream(),
+            word_pos: rng.get_word_pos(),
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ChaCha8Rng, D::Error> {
+        let state = RngState::deserialize(deserializer)?;
+        let mut rng = ChaCha8Rng::from_seed(state.seed);
+        rng.set_stream(state.stream);
+        rng.set_word_pos(state.word_pos);
+        Ok(rng)
+    }
+}
+
+// Degradado de la llama: azul al nacer, rojo al apagarse
+fn flame_color(life_fraction: f32) -> Rgba {
+    let t = 1.0 - life_fraction;
+    if t < 0.2 {
+        Rgba::BLUE
+    } else if t < 0.4 {
+        Rgba::WHITE
+    } else if t < 0.6 {
+        Rgba::YELLOW
+    } else if t < 0.8 {
+        Rgba::ORANGE
+    } else {
+        Rgba::RED
+    }
+}
+
+// Un paso de una bala: primero la velocidad y luego la posición, como la nave
+fn advance_bullet(pos: &mut (f32, f32), vel: &mut (f32, f32), gravity: (f32, f32), dt: f32) {
+    vel.0 += gravity.0 * dt;
+    vel.1 += gravity.1 * dt;
+    *pos = wrap_position((pos.0 + vel.0 * dt, pos.1 + vel.1 * dt));
+}
+
+// Dos asteroides que se tocan se funden si van más despacio que una fracción
+// de su velocidad de escape y el resultado no sale demasiado grande. Si se
+// funden, devuelve la velocidad relativa del choque.
+fn should_merge(config: &Config, asteroid1: &Asteroid, asteroid2: &Asteroid) -> Option<f32> {
+    let merged_size = (asteroid1.size * asteroid1.size + asteroid2.size * asteroid2.size).sqrt();
+    if merged_size > config.asteroids.max_merge_size {
+        return None;
+    }
+    let delta = wrapped_delta(asteroid1.pos, asteroid2.pos);
+    let escape = escape_speed(&config.physics, asteroid1.mass, asteroid2.mass, delta);
+    let relative_speed =
+        ((asteroid2.vel.0 - asteroid1.vel.0).powi(2) + (asteroid2.vel.1 - asteroid1.vel.1).powi(2)).sqrt();
+    (relative_speed < config.physics.accretion * escape).then_some(relative_speed)
+}
+
+impl World {
+    // Toda la aleatoriedad sale de un único generador sembrado: misma semilla, misma partida
+    pub fn new(seed: u64, config: Config) -> World {
+        let mut world = World {
+            player_pos: (WINDOW_WIDTH / 2.0, WINDOW_HEIGHT / 2.0),
+            player_prev_pos: (WINDOW_WIDTH / 2.0, WINDOW_HEIGHT / 2.0),
+            player_vel: (0.0, 0.0),
+            player_angle: 0.0,
+            bullets: Vec::new(),
+            asteroids: Vec::new(),
+            is_game_over: false,
+            destroyed_count: 0.0,
+            particles: Vec::new(),
+            level: 1,
+            score: 0,
+            lives: config.player.lives,
+            next_extra_life: config.player.extra_life_score,
+            invulnerable_time: 0.0,
+            fire_cooldown: 0.0,
+            thruster_particles: Vec::new(),
+            seed,
+            tick: 0,
+            config,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            grid: SpatialGrid::default(),
+        };
+
+        for _ in 0..world.config.asteroids.count {
+            let mut asteroid = world.random_asteroid(1.0);
+            asteroid.wobble(4.0);
+            world.asteroids.push(asteroid);
+        }
+
+        world
+    }
+
+    pub fn step(&mut self, dt: f32, input: &Input) {
+        self.tick += 1;
+        self.store_previous_positions();
+        self.apply_input(input);
+
+        if self.is_game_over {
+            return;
+        }
+
+        self.update_player(dt, input);
+        self.update_bullets(dt);
+        self.update_asteroids(dt);
+        self.update_particles(dt);
+        self.check_collisions();
+    }
+
+    // Energía y momento de los asteroides en este tick
+    pub fn diagnostics(&self) -> Diagnostics {
+        Diagnostics::measure(self.tick, &self.config.physics, &self.asteroids)
+    }
+
+    // Triángulo de la nave girado según su ángulo, relativo a su posición
+    pub fn ship_outline(&self) -> [(f32, f32); 3] {
+        // El triángulo apunta hacia -y: hay que girarlo un cuarto de vuelta más
+        SHIP_POINTS.map(|point| rotate(point, self.player_angle + std::f32::consts::FRAC_PI_2))
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerable_time > 0.0
+    }
+
+    // Guarda las posiciones del paso anterior para interpolar el dibujo
+    fn store_previous_positions(&mut self) {
+        self.player_prev_pos = self.player_pos;
+        for bullet in &mut self.bullets {
+            bullet.prev_pos = bullet.pos;
+        }
+        for asteroid in &mut self.asteroids {
+            asteroid.prev_pos = asteroid.pos;
+        }
+    }
+
+    fn apply_input(&mut self, input: &Input) {
+        if input.fire {
+            self.shoot();
+        }
+    }
+
+    fn update_player(&mut self, dt: f32, input: &Input) {
+        self.invulnerable_time = (self.invulnerable_time - dt).max(0.0);
+
+        // Giro y empuje continuos mientras la tecla está pulsada
+        if input.rotate_left {
+            self.player_angle -= self.config.player.rotation_speed * dt;
+        }
+        if input.rotate_right {
+            self.player_angle += self.config.player.rotation_speed * dt;
+        }
+        if input.thrust {
+            let player = &self.config.player;
+            let (dir_x, dir_y) = (self.player_angle.cos(), self.player_angle.sin());
+            self.player_vel.0 += dir_x * player.thrust * dt;
+            self.player_vel.1 += dir_y * player.thrust * dt;
+            // Limitar la velocidad máxima
+            let speed = (self.player_vel.0.powi(2) + self.player_vel.1.powi(2)).sqrt();
+            if speed > player.max_speed {
+                self.player_vel.0 = self.player_vel.0 / speed * player.max_speed;
+                self.player_vel.1 = self.player_vel.1 / speed * player.max_speed;
+            }
+        }
+
+        let gravity = self.gravity_at(self.player_pos);
+        self.player_vel.0 += gravity.0 * dt;
+        self.player_vel.1 += gravity.1 * dt;
+
+        self.player_pos.0 += self.player_vel.0 * dt;
+        self.player_pos.1 += self.player_vel.1 * dt;
+
+        // Wrap around the screen
+        self.player_pos = wrap_position(self.player_pos);
+
+        // Apply friction to slow down the player
+        self.player_vel.0 *= self.config.player.friction;
+        self.player_vel.1 *= self.config.player.friction;
+
+        // Generar nuevas partículas
+        self.generate_thruster_particles();
+
+        // Actualizar partículas existentes
+        self.update_thruster_particles(dt);
+
+        // Generar partículas del propulsor
+        let speed = (self.player_vel.0.powi(2) + self.player_vel.1.powi(2)).sqrt();
+        let normalized_speed = speed / self.config.player.max_speed;
+        let inverse_speed_factor = 1.0 - normalized_speed;
+        let num_particles = (inverse_speed_factor * 10.0).max(1.0) as usize; // Ajusta estos valores según necesites
+
+        for _ in 0..num_particles {
+            let angle = self.player_angle + std::f32::consts::PI + self.rng.gen_range(-0.2..0.2);
+            let particle_speed = self.rng.gen_range(20.0..50.0) * (1.0 + inverse_speed_factor);
+            let vel = (angle.cos() * particle_speed, angle.sin() * particle_speed);
+
+            let pos = (
+                self.player_pos.0 - self.player_angle.cos() * PLAYER_SIZE,
+                self.player_pos.1 - self.player_angle.sin() * PLAYER_SIZE,
+            );
+
+            let lifetime =
+                self.rng.gen_range(0.3..self.config.particles.thruster_lifetime) * (1.0 + inverse_speed_factor);
+            let color = flame_color(1.0);
+
+            self.thruster_particles.push(ThrusterParticle::new(pos, vel, color, lifetime));
+        }
+
+        // Limitar el número máximo de partículas
+        while self.thruster_particles.len() > self.config.particles.max_thruster_particles {
+            self.thruster_particles.remove(0);
+        }
+    }
+
+    // Aceleración que produce la gravedad de los asteroides sobre la nave en
+    // `pos`, con el mismo límite que la de los asteroides; las balas con
+    // gravedad sienten la misma
+    fn gravity_at(&self, pos: (f32, f32)) -> (f32, f32) {
+        let mut total_gravity = (0.0, 0.0);
+
+        for asteroid in &self.asteroids {
+            let force = calculate_gravity(
+                &self.config.physics,
+                PLAYER_SIZE * PLAYER_SIZE * std::f32::consts::PI,
+                asteroid.mass,
+                wrapped_delta(pos, asteroid.pos),
+            );
+            total_gravity.0 += force.0;
+            total_gravity.1 += force.1;
+        }
+
+        let gravity_factor = self.config.physics.player_gravity_factor;
+        clamp_magnitude(
+            (total_gravity.0 * gravity_factor, total_gravity.1 * gravity_factor),
+            self.config.asteroids.max_acceleration,
+        )
+    }
+
+    fn generate_thruster_particles(&mut self) {
+        let speed = (self.player_vel.0.powi(2) + self.player_vel.1.powi(2)).sqrt();
+        let num_particles = (speed / 10.0).min(5.0) as usize;
+
+        for _ in 0..num_particles {
+            let angle = self.player_angle + std::f32::consts::PI + self.rng.gen_range(-0.2..0.2);
+            let speed = self.rng.gen_range(50.0..100.0);
+            let vel = (angle.cos() * speed, angle.sin() * speed);
+
+            let pos = (
+                self.player_pos.0 - self.player_angle.cos() * PLAYER_SIZE,
+                self.player_pos.1 - self.player_angle.sin() * PLAYER_SIZE,
+            );
+
+            let lifetime = self.rng.gen_range(0.3..self.config.particles.thruster_lifetime);
+            let color = flame_color(1.0); // Iniciar con el color azul
+
+            self.thruster_particles.push(ThrusterParticle::new(pos, vel, color, lifetime));
+        }
+    }
+
+    fn update_thruster_particles(&mut self, dt: f32) {
+        let mut i = 0;
+        while i < self.thruster_particles.len() {
+            let remove_particle = {
+                let particle = &mut self.thruster_particles[i];
+                particle.update(dt);
+                particle.color = flame_color(particle.life / particle.initial_life);
+                particle.life <= 0.0
+            };
+
+            if remove_particle {
+                self.thruster_particles.swap_remove(i);
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    fn update_bullets(&mut self, dt: f32) {
+        self.fire_cooldown = (self.fire_cooldown - dt).max(0.0);
+
+        let gravity: Vec<(f32, f32)> = if self.config.bullets.gravity {
+            self.bullets.iter().map(|bullet| self.gravity_at(bullet.pos)).collect()
+        } else {
+            vec![(0.0, 0.0); self.bullets.len()]
+        };
+
+        // Las balas dan la vuelta como todo lo demás y desaparecen al agotar su vida
+        for (bullet, gravity) in self.bullets.iter_mut().zip(gravity) {
+            advance_bullet(&mut bullet.pos, &mut bullet.vel, gravity, dt);
+            bullet.life -= dt;
+        }
+        self.bullets.retain(|bullet| bullet.life > 0.0);
+    }
+
+    fn update_asteroids(&mut self, dt: f32) {
+        let max_acceleration = self.config.asteroids.max_acceleration;
+        integrate(&self.config.physics, max_acceleration, &mut self.asteroids, dt);
+
+        // Limitar la velocidad máxima
+        let max_speed = self.config.asteroids.max_speed;
+        for asteroid in &mut self.asteroids {
+            let speed = (asteroid.vel.0.powi(2) + asteroid.vel.1.powi(2)).sqrt();
+            if speed > max_speed {
+                asteroid.vel.0 = asteroid.vel.0 / speed * max_speed;
+                asteroid.vel.1 = asteroid.vel.1 / speed * max_speed;
+            }
+        }
+
+        for asteroid in &mut self.asteroids {
+            asteroid.angle = (asteroid.angle + asteroid.angular_vel * dt).rem_euclid(std::f32::consts::TAU);
+            asteroid.update_deformation(dt, &self.config.physics);
+        }
+
+        self.handle_asteroid_collisions();
+    }
+
+    // Mete los asteroides en la rejilla y devuelve el radio del mayor contorno
+    // deformado, que hay que sumar al radio de las consultas
+    fn build_asteroid_grid(&mut self) -> f32 {
+        let max_size = self.asteroids.iter().map(Asteroid::bounding_radius).fold(0.0, f32::max);
+        self.grid.build(2.0 * max_size, self.asteroids.iter().map(|asteroid| asteroid.pos));
+        max_size
+    }
+
+    fn handle_asteroid_collisions(&mut self) {
+        let max_size = self.build_asteroid_grid();
+        let mut collisions = Vec::new();
+        let mut candidates = Vec::new();
+
+        // Detectar colisiones entre los candidatos de la rejilla, en el mismo
+        // orden (i, j) que una búsqueda exhaustiva
+        for i in 0..self.asteroids.len() {
+            let asteroid1 = &self.asteroids[i];
+            self.grid.query_sorted(asteroid1.pos, asteroid1.bounding_radius() + max_size, &mut candidates);
+            for &j in candidates.iter().filter(|&&j| j > i) {
+                if asteroid1.overlaps(&self.asteroids[j]) {
+                    collisions.push((i, j));
+                }
+            }
+        }
+
+        // Resolver colisiones
+        for (i, j) in collisions {
+            let (asteroid1, asteroid2) = self.asteroids.split_at_mut(j);
+            let asteroid1 = &mut asteroid1[i];
+            let asteroid2 = &mut asteroid2[0];
+            // Uno de los dos ya se ha fundido con otro en este tick
+            if asteroid1.is_destroyed || asteroid2.is_destroyed {
+                continue;
+            }
+
+            // Acreción: si chocan tan despacio que su gravedad los mantiene
+            // juntos, se funden en uno, y el golpe hace vibrar el contorno
+            if let Some(relative_speed) = should_merge(&self.config, asteroid1, asteroid2) {
+                *asteroid1 = merge(asteroid1, asteroid2, self.config.asteroids.jaggedness, &mut self.rng);
+                asteroid1.wobble(4.0 + relative_speed);
+                asteroid2.is_destroyed = true;
+                continue;
+            }
+
+            // Calcular la normal de colisión, también entre asteroides a
+            // lados opuestos de un borde
+            let (nx, ny) = wrapped_delta(asteroid1.pos, asteroid2.pos);
+            let d = (nx * nx + ny * ny).sqrt();
+            // Dos asteroides en el mismo punto no tienen normal: se separan en horizontal
+            let (nx, ny) = if d > 0.0 { (nx / d, ny / d) } else { (1.0, 0.0) };
+
+            // Lo que se solapan sus contornos deformados a lo largo de la normal
+            let extent1 = asteroid1.extent((nx, ny));
+            let overlap = extent1 + asteroid2.extent((-nx, -ny)) - d;
+            // Otro choque de este tick ya los ha separado, o los contornos solo
+            // se cruzan de lado: no hay nada que separar ni que frenar
+            if overlap <= 0.0 {
+                continue;
+            }
+
+            // Punto de contacto en mitad del solape, relativo a cada centro
+            let reach = extent1 - overlap / 2.0;
+            let r1 = (reach * nx, reach * ny);
+            let r2 = (r1.0 - d * nx, r1.1 - d * ny);
+
+            // Velocidad relativa en el punto de contacto, contando el giro
+            let point_vel = |asteroid: &Asteroid, r: (f32, f32)| {
+                (asteroid.vel.0 - asteroid.angular_vel * r.1, asteroid.vel.1 + asteroid.angular_vel * r.0)
+            };
+            let (v1, v2) = (point_vel(asteroid1, r1), point_vel(asteroid2, r2));
+            let (dvx, dvy) = (v2.0 - v1.0, v2.1 - v1.1);
+            let normal_speed = dvx * nx + dvy * ny;
+            let impact_speed = normal_speed.abs();
+
+            // Impulso elástico en la normal y rozamiento de Coulomb en la
+            // tangente, que es lo que hace girar a los asteroides en un roce.
+            // Solo si se acercan: si ya se separan, el impulso los volvería a juntar.
+            if normal_speed < 0.0 {
+                let (tx, ty) = (-ny, nx);
+                let tangent_speed = dvx * tx + dvy * ty;
+                let resistance = |direction: (f32, f32)| {
+                    let c1 = r1.0 * direction.1 - r1.1 * direction.0;
+                    let c2 = r2.0 * direction.1 - r2.1 * direction.0;
+                    1.0 / asteroid1.mass + 1.0 / asteroid2.mass + c1 * c1 / asteroid1.inertia + c2 * c2 / asteroid2.inertia
+                };
+                let normal_impulse = -2.0 * normal_speed / resistance((nx, ny));
+                let max_friction = self.config.physics.friction * normal_impulse;
+                let tangent_impulse = (-tangent_speed / resistance((tx, ty))).clamp(-max_friction, max_friction);
+
+                // El impulso actúa sobre el segundo asteroide y el opuesto sobre el primero
+                let impulse = (normal_impulse * nx + tangent_impulse * tx, normal_impulse * ny + tangent_impulse * ty);
+                asteroid1.vel.0 -= impulse.0 / asteroid1.mass;
+                asteroid1.vel.1 -= impulse.1 / asteroid1.mass;
+                asteroid1.angular_vel -= (r1.0 * impulse.1 - r1.1 * impulse.0) / asteroid1.inertia;
+                asteroid2.vel.0 += impulse.0 / asteroid2.mass;
+                asteroid2.vel.1 += impulse.1 / asteroid2.mass;
+                asteroid2.angular_vel += (r2.0 * impulse.1 - r2.1 * impulse.0) / asteroid2.inertia;
+            }
+
+            // Separar los asteroides para evitar superposición
+            let separation = overlap / 2.0;
+            asteroid1.pos.0 -= separation * nx;
+            asteroid1.pos.1 -= separation * ny;
+            asteroid2.pos.0 += separation * nx;
+            asteroid2.pos.1 += separation * ny;
+            asteroid1.pos = wrap_position(asteroid1.pos);
+            asteroid2.pos = wrap_position(asteroid2.pos);
+
+            // Abollar cada asteroide por el lado del choque
+            let dent_speed = self.config.physics.dent_strength * impact_speed;
+            asteroid1.dent((nx, ny), dent_speed);
+            asteroid2.dent((-nx, -ny), dent_speed);
+        }
+
+        self.asteroids.retain(|asteroid| !asteroid.is_destroyed);
+    }
+
+    fn check_collisions(&mut self) {
+        let mut should_level_up = false;
+
+        let max_size = self.build_asteroid_grid();
+        let mut candidates = Vec::new();
+
+        // Check for collisions between player and asteroids. La vida se pierde
+        // al final, para no descartar los disparos que aciertan en este tick.
+        let mut player_hit = false;
+        if !self.is_invulnerable() {
+            let ship = self.ship_outline();
+            // Las esquinas traseras del triángulo quedan a algo más de PLAYER_SIZE del centro
+            self.grid.query(self.player_pos, 1.5 * PLAYER_SIZE + max_size, |i| {
+                player_hit |= self.asteroids[i].overlaps_polygon(self.player_pos, &ship);
+            });
+        }
+
+        // Check for collisions between bullets and asteroids
+        let mut new_asteroids = Vec::new();
+        let mut bullets_to_remove = Vec::new();
+        let mut explosions_to_generate = Vec::new();
+
+        for (bullet_idx, bullet) in self.bullets.iter().enumerate() {
+            self.grid.query_sorted(bullet.pos, max_size, &mut candidates);
+            for &i in &candidates {
+                let asteroid = &mut self.asteroids[i];
+                if asteroid.is_destroyed {
+                    continue;
+                }
+                if asteroid.contains(bullet.pos) {
+                    bullets_to_remove.push(bullet_idx);
+                    asteroid.is_destroyed = true;
+                    self.destroyed_count += 1.0;
+
+                    let points = (100.0 / asteroid.size) as u32 * self.level;
+                    self.score += points;
+
+                    explosions_to_generate.push((asteroid.pos, 20, Rgba::GREEN));
+
+                    // Dividir el asteroide en piezas más pequeñas
+                    new_asteroids.extend(fragment(asteroid, bullet, &self.config, &mut self.rng));
+
+                    if self.destroyed_count >= self.config.asteroids.level_up_threshold * self.level as f32 {
+                        should_level_up = true;
+                    }
+
+                    // La bala se gasta en el primer asteroide que toca
+                    break;
+                }
+            }
+        }
+
+        // Remove hit bullets
+        bullets_to_remove.sort_unstable_by(|a, b| b.cmp(a));
+        bullets_to_remove.dedup();
+        for idx in bullets_to_remove {
+            self.bullets.remove(idx);
+        }
+
+        self.asteroids.extend(new_asteroids);
+        self.asteroids.retain(|asteroid| !asteroid.is_destroyed);
+
+        // Vidas extra al superar cada umbral de puntuación
+        while self.score >= self.next_extra_life {
+            self.lives += 1;
+            self.next_extra_life += self.config.player.extra_life_score;
+        }
+
+        // Generate explosions
+        for (pos, num_particles, color) in explosions_to_generate {
+            self.generate_explosion(pos, num_particles, color);
+        }
+
+        if should_level_up {
+            self.level_up();
+        }
+
+        if player_hit {
+            self.lose_life();
+        }
+    }
+
+    fn lose_life(&mut self) {
+        self.generate_explosion(self.player_pos, 50, Rgba::RED);
+        self.lives -= 1;
+        if self.lives == 0 {
+            self.is_game_over = true;
+            return;
+        }
+
+        self.player_pos = self.find_safe_spawn();
+        self.player_prev_pos = self.player_pos;
+        self.player_vel = (0.0, 0.0);
+        self.player_angle = 0.0;
+        self.invulnerable_time = self.config.player.invulnerability_time;
+    }
+
+    // Distancia libre entre un punto y el círculo que contiene el asteroide más cercano
+    fn clearance(&self, pos: (f32, f32)) -> f32 {
+        self.asteroids
+            .iter()
+            .map(|asteroid| wrapped_distance(pos, asteroid.pos) - asteroid.bounding_radius())
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    // Busca un punto sin asteroides cerca, empezando por el centro. Si no hay
+    // ninguno lo bastante despejado, se queda con el mejor candidato.
+    fn find_safe_spawn(&mut self) -> (f32, f32) {
+        let mut best = (WINDOW_WIDTH / 2.0, WINDOW_HEIGHT / 2.0);
+        let mut best_clearance = self.clearance(best);
+
+        for _ in 0..RESPAWN_ATTEMPTS {
+            if best_clearance >= self.config.player.respawn_safe_radius {
+                break;
+            }
+            let candidate = (self.rng.gen_range(0.0..WINDOW_WIDTH), self.rng.gen_range(0.0..WINDOW_HEIGHT));
+            let clearance = self.clearance(candidate);
+            if clearance > best_clearance {
+                best = candidate;
+                best_clearance = clearance;
+            }
+        }
+
+        best
+    }
+
+    // Asteroide en una posición aleatoria, con la velocidad escalada por `speed_factor`
+    fn random_asteroid(&mut self, speed_factor: f32) -> Asteroid {
+        let asteroids = &self.config.asteroids;
+        let x = self.rng.gen_range(0.0..WINDOW_WIDTH);
+        let y = self.rng.gen_range(0.0..WINDOW_HEIGHT);
+        let vel_x = self.rng.gen_range(-asteroids.initial_speed..asteroids.initial_speed) * speed_factor;
+        let vel_y = self.rng.gen_range(-asteroids.initial_speed..asteroids.initial_speed) * speed_factor;
+        let size = self.rng.gen_range(asteroids.min_size..asteroids.max_size);
+        Asteroid::jagged((x, y), (vel_x, vel_y), size, asteroids.jaggedness, &mut self.rng)
+    }
+
+    fn level_up(&mut self) {
+        self.level += 1;
+        // Aumentar la dificultad
+        for _ in 0..self.level * self.config.asteroids.spawn_per_level {
+            let mut asteroid = self.random_asteroid(1.0 + self.level as f32 * 0.1);
+            asteroid.wobble(6.0);
+            self.asteroids.push(asteroid);
+        }
+    }
+
+    fn generate_explosion(&mut self, pos: (f32, f32), num_particles: usize, color: Rgba) {
+        for _ in 0..num_particles {
+            let angle = self.rng.gen_range(0.0..std::f32::consts::TAU);
+            let speed = self.rng.gen_range(0.0..self.config.particles.speed);
+            self.particles.push(Particle {
+                pos,
+                vel: (speed * angle.cos(), speed * angle.sin()),
+                life: self.config.particles.lifetime,
+                color,
+            });
+        }
+    }
+
+    fn update_particles(&mut self, dt: f32) {
+        for particle in &mut self.particles {
+            particle.pos.0 += particle.vel.0 * dt;
+            particle.pos.1 += particle.vel.1 * dt;
+            particle.life -= dt;
+        }
+        self.particles.retain(|particle| particle.life > 0.0);
+    }
+
+    // Trayectoria de una bala disparada ahora durante `seconds` segundos (como
+    // mucho su vida), un punto por tick. Los asteroides se toman quietos, así
+    // que es exacta a corto plazo; se corta al chocar con uno.
+    pub fn predict_bullet_path(&self, seconds: f32) -> Vec<(f32, f32)> {
+        let bullets = &self.config.bullets;
+        let mut pos = self.player_pos;
+        let mut vel = (self.player_angle.cos() * bullets.speed, self.player_angle.sin() * bullets.speed);
+        let ticks = (seconds.min(bullets.lifetime) / TICK_DT) as usize;
+
+        let mut path = Vec::with_capacity(ticks);
+        for _ in 0..ticks {
+            let gravity = if bullets.gravity { self.gravity_at(pos) } else { (0.0, 0.0) };
+            advance_bullet(&mut pos, &mut vel, gravity, TICK_DT);
+            path.push(pos);
+            if self.asteroids.iter().any(|asteroid| asteroid.contains(pos)) {
+                break;
+            }
+        }
+        path
+    }
+
+    // Con la repetición de teclas del sistema llegan disparos seguidos: se
+    // ignoran los que caen dentro del tiempo de recarga o por encima del máximo
+    fn shoot(&mut self) {
+        let bullets = &self.config.bullets;
+        if self.fire_cooldown > 0.0 || self.bullets.len() >= bullets.max_bullets {
+            return;
+        }
+        let (dir_x, dir_y) = (self.player_angle.cos(), self.player_angle.sin());
+        let vel = (dir_x * bullets.speed, dir_y * bullets.speed);
+        self.bullets.push(Bullet::new(self.player_pos, vel, bullets.lifetime));
+        self.fire_cooldown = bullets.fire_cooldown;
+    }
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use bubbleroid::{merge, wrapped_delta, Asteroid, Config, Input, World};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use common::empty_world;
+
+const SIZE: f32 = 20.0;
+
+// Dos asteroides que se tocan, acercándose a `speed` px/s entre los dos
+fn touching(config: Config, speed: f32, size: f32) -> World {
+    let mut world = empty_world(config);
+    world.asteroids.push(Asteroid::new((600.0, 360.0), (speed / 2.0, 0.0), size));
+    world.asteroids.push(Asteroid::new((600.0 + 1.9 * size, 360.0), (-speed / 2.0, 0.0), size));
+    world.step(1.0 / 120.0, &Input::default());
+    world
+}
+
+#[test]
+fn slow_touch_merges() {
+    let world = touching(Config::default(), 2.0, SIZE);
+    assert_eq!(world.asteroids.len(), 1);
+
+    let merged = &world.asteroids[0];
+    let mass = 2.0 * Asteroid::new((0.0, 0.0), (0.0, 0.0), SIZE).mass;
+    assert!((merged.mass - mass).abs() < mass * 1e-4, "{} frente a {}", merged.mass, mass);
+    assert!((merged.size - SIZE * 2.0_f32.sqrt()).abs() < 1e-3);
+    // En el centro de masas, y el golpe hace vibrar el contorno
+    assert!(wrapped_delta(merged.pos, (600.0 + 0.95 * SIZE, 360.0)).0.abs() < 0.1, "{:?}", merged.pos);
+    assert!(merged.vertices.iter().any(|vertex| vertex.vel != (0.0, 0.0)));
+}
+
+#[test]
+fn fast_hit_bounces() {
+    let world = touching(Config::default(), 150.0, SIZE);
+    assert_eq!(world.asteroids.len(), 2);
+}
+
+#[test]
+fn accretion_can_be_disabled() {
+    let mut config = Config::default();
+    config.physics.accretion = 0.0;
+    let world = touching(config, 2.0, SIZE);
+    assert_eq!(world.asteroids.len(), 2);
+}
+
+#[test]
+fn merged_size_is_capped() {
+    let config = Config::default();
+    let size = config.asteroids.max_merge_size / 1.2;
+    let world = touching(config, 0.0, size);
+    assert_eq!(world.asteroids.len(), 2);
+}
+
+#[test]
+fn merge_conserves_momentum() {
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+    let mut first = Asteroid::new((10.0, 360.0), (12.0, -3.0), 25.0);
+    first.angular_vel = 0.7;
+    // Al otro lado del borde izquierdo
+    let mut second = Asteroid::new((1260.0, 380.0), (-4.0, 5.0), 15.0);
+    second.angular_vel = -1.5;
+
+    let merged = merge(&first, &second, 0.25, &mut rng);
+    assert!((merged.mass - first.mass - second.mass).abs() < merged.mass * 1e-4);
+
+    let momentum = (
+        first.mass * first.vel.0 + second.mass * second.vel.0,
+        first.mass * first.vel.1 + second.mass * second.vel.1,
+    );
+    assert!((merged.mass * merged.vel.0 - momentum.0).abs() < 1e-2 * merged.mass);
+    assert!((merged.mass * merged.vel.1 - momentum.1).abs() < 1e-2 * merged.mass);
+
+    // Momento angular respecto al centro del asteroide nuevo
+    let angular = |asteroid: &Asteroid| {
+        let r = wrapped_delta(merged.pos, asteroid.pos);
+        let v = (asteroid.vel.0 - merged.vel.0, asteroid.vel.1 - merged.vel.1);
+        asteroid.inertia * asteroid.angular_vel + asteroid.mass * (r.0 * v.1 - r.1 * v.0)
+    };
+    let before = angular(&first) + angular(&second);
+    let after = merged.inertia * merged.angular_vel;
+    assert!((after - before).abs() < before.abs() * 1e-3, "{} frente a {}", after, before);
+}
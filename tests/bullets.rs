@@ -0,0 +1,117 @@
+mod common;
+
+use bubbleroid::constants::{TICK_DT, WINDOW_WIDTH};
+use bubbleroid::{wrapped_distance, Asteroid, Config, Input, World};
+use common::empty_world;
+
+const FIRE: Input = Input { thrust: false, rotate_left: false, rotate_right: false, fire: true };
+const IDLE: Input = Input { thrust: false, rotate_left: false, rotate_right: false, fire: false };
+
+// Ticks que hacen falta para cubrir `seconds`
+fn ticks(seconds: f32) -> u32 {
+    (seconds / TICK_DT).ceil() as u32
+}
+
+#[test]
+fn bullet_wraps_across_the_edge() {
+    let mut world = empty_world(Config::default());
+    // Cerca del borde derecho, mirando hacia +x
+    world.player_pos = (WINDOW_WIDTH - 20.0, 360.0);
+    world.step(TICK_DT, &FIRE);
+    assert_eq!(world.bullets.len(), 1);
+
+    let mut wrapped = false;
+    for _ in 0..ticks(0.2) {
+        let before = world.bullets[0].pos;
+        world.step(TICK_DT, &IDLE);
+        let after = world.bullets[0].pos;
+        assert!((0.0..WINDOW_WIDTH).contains(&after.0), "la bala se ha salido a {:?}", after);
+        assert_eq!(after.1, 360.0);
+        wrapped |= after.0 < before.0;
+    }
+    assert!(wrapped, "la bala no ha dado la vuelta");
+    assert!(world.bullets[0].pos.0 < 100.0, "{:?}", world.bullets[0].pos);
+}
+
+#[test]
+fn bullet_expires_after_its_lifetime() {
+    let config = Config::default();
+    let lifetime = config.bullets.lifetime;
+    let mut world = empty_world(config);
+    world.step(TICK_DT, &FIRE);
+    for _ in 1..ticks(lifetime) - 1 {
+        world.step(TICK_DT, &IDLE);
+    }
+    assert_eq!(world.bullets.len(), 1, "la bala ha desaparecido antes de tiempo");
+
+    for _ in 0..2 {
+        world.step(TICK_DT, &IDLE);
+    }
+    assert!(world.bullets.is_empty(), "la bala sigue viva tras {} s", lifetime);
+}
+
+#[test]
+fn fire_stops_at_max_bullets() {
+    let mut config = Config::default();
+    config.bullets.max_bullets = 3;
+    config.bullets.fire_cooldown = 0.0;
+    let mut world = empty_world(config);
+    for _ in 0..10 {
+        world.step(TICK_DT, &FIRE);
+    }
+    assert_eq!(world.bullets.len(), 3);
+}
+
+#[test]
+fn cooldown_drops_repeated_fire() {
+    let mut config = Config::default();
+    let cooldown = config.bullets.fire_cooldown;
+    config.bullets.max_bullets = 100;
+    let mut world = empty_world(config);
+
+    // Disparos en cada tick, como llegan con la repetición de teclas
+    for _ in 0..ticks(cooldown) {
+        world.step(TICK_DT, &FIRE);
+    }
+    assert_eq!(world.bullets.len(), 1, "se han colado disparos durante la recarga");
+
+    world.step(TICK_DT, &FIRE);
+    assert_eq!(world.bullets.len(), 2, "la recarga no ha terminado");
+
+    // Un segundo disparando sin parar da uno por cada recarga
+    for _ in 0..ticks(1.0) {
+        world.step(TICK_DT, &FIRE);
+    }
+    let expected = 2 + (1.0 / cooldown) as usize;
+    assert!(world.bullets.len().abs_diff(expected) <= 1, "{} balas, se esperaban {}", world.bullets.len(), expected);
+}
+
+// Una bala disparada hacia +x que pasa a 50 px por encima de un asteroide quieto
+fn shot_past_an_asteroid(gravity: bool) -> World {
+    let mut config = Config::default();
+    config.bullets.gravity = gravity;
+    let mut world = empty_world(config);
+    world.player_pos = (400.0, 360.0);
+    world.asteroids.push(Asteroid::new((640.0, 410.0), (0.0, 0.0), 40.0));
+    world
+}
+
+#[test]
+fn gravity_bends_the_bullet_toward_the_asteroid() {
+    // Sin gravedad va recta
+    let straight = shot_past_an_asteroid(false).predict_bullet_path(1.0);
+    assert!(straight.iter().all(|pos| pos.1 == 360.0));
+
+    let mut world = shot_past_an_asteroid(true);
+    let path = world.predict_bullet_path(1.0);
+    assert_eq!(path.len(), straight.len(), "la previsión choca con el asteroide");
+
+    // La previsión es el camino que sigue la bala de verdad, tick a tick
+    for (tick, &expected) in path.iter().enumerate() {
+        world.step(TICK_DT, if tick == 0 { &FIRE } else { &IDLE });
+        let bullet = world.bullets[0].pos;
+        assert!(wrapped_distance(expected, bullet) < 1e-3, "tick {}: {:?} frente a {:?}", tick, expected, bullet);
+    }
+    let end = world.bullets[0].pos;
+    assert!(end.1 > 365.0, "la bala apenas se ha desviado: {:?}", end);
+}
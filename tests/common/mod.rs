@@ -0,0 +1,18 @@
+use bubbleroid::{Config, World};
+
+// Mundo sin asteroides al azar y con la nave en una esquina, lejos de las pruebas
+pub fn empty_world(config: Config) -> World {
+    let mut config = config;
+    config.asteroids.count = 0;
+    let mut world = World::new(1, config);
+    world.player_pos = (100.0, 100.0);
+    world
+}
+
+// Asteroides quietos que se tocan se fundirían: para las pruebas que quieren que choquen
+#[allow(dead_code)]
+pub fn without_merging() -> Config {
+    let mut config = Config::default();
+    config.physics.accretion = 0.0;
+    config
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use bubbleroid::{point_in_polygon, wrapped_distance, Asteroid, Bullet, Input, World};
+use common::{empty_world, without_merging};
+
+const CENTER: (f32, f32) = (640.0, 360.0);
+const SIZE: f32 = 20.0;
+// El contorno se estira en x y se aplasta en y
+const STRETCH: f32 = 1.5;
+const SQUEEZE: f32 = 0.7;
+
+// Asteroide con forma de óvalo: su borde llega a SIZE * STRETCH en x
+fn stretched(pos: (f32, f32)) -> Asteroid {
+    let mut asteroid = Asteroid::new(pos, (0.0, 0.0), SIZE);
+    for vertex in &mut asteroid.vertices {
+        vertex.offset = (vertex.rest.0 * STRETCH, vertex.rest.1 * SQUEEZE);
+    }
+    asteroid
+}
+
+fn shoot_at(asteroid: Asteroid, bullet: (f32, f32)) -> World {
+    let mut world = empty_world(without_merging());
+    world.asteroids.push(asteroid);
+    world.bullets.push(Bullet::new(bullet, (0.0, 0.0), 1.0));
+    world.step(1.0 / 120.0, &Input::default());
+    world
+}
+
+#[test]
+fn bullet_hits_the_bulge_on_the_stretched_axis() {
+    // Fuera del radio de reposo pero dentro del contorno deformado
+    let distance = SIZE * 1.3;
+    for bullet in [(CENTER.0 + distance, CENTER.1), (CENTER.0 - distance, CENTER.1)] {
+        let world = shoot_at(stretched(CENTER), bullet);
+        assert!(world.asteroids.is_empty(), "la bala en {:?} ha atravesado el abultamiento", bullet);
+        assert!(world.bullets.is_empty());
+        assert!(world.score > 0);
+    }
+
+    // Sin deformar, el mismo disparo no toca nada
+    let world = shoot_at(Asteroid::new(CENTER, (0.0, 0.0), SIZE), (CENTER.0 + distance, CENTER.1));
+    assert_eq!(world.asteroids.len(), 1);
+    assert_eq!(world.bullets.len(), 1);
+}
+
+#[test]
+fn bullet_misses_the_squeezed_axis() {
+    // Dentro del radio de reposo pero fuera del contorno aplastado
+    let world = shoot_at(stretched(CENTER), (CENTER.0, CENTER.1 + SIZE * 0.85));
+    assert_eq!(world.asteroids.len(), 1);
+    assert_eq!(world.bullets.len(), 1);
+    assert_eq!(world.score, 0);
+}
+
+#[test]
+fn ship_dies_against_the_bulge() {
+    // La nave mira hacia +x con la punta a PLAYER_SIZE del centro; el borde
+    // del asteroide queda a 10 px de ella en reposo y la cruza estirado
+    let asteroid_pos = (CENTER.0 + 40.0, CENTER.1);
+
+    let mut world = empty_world(without_merging());
+    let lives = world.lives;
+    world.player_pos = CENTER;
+    world.asteroids.push(stretched(asteroid_pos));
+    world.step(1.0 / 120.0, &Input::default());
+    assert_eq!(world.lives, lives - 1, "la nave ha sobrevivido dentro del contorno");
+
+    let mut world = empty_world(without_merging());
+    world.player_pos = CENTER;
+    world.asteroids.push(Asteroid::new(asteroid_pos, (0.0, 0.0), SIZE));
+    world.step(1.0 / 120.0, &Input::default());
+    assert_eq!(world.lives, lives, "la nave ha chocado con el radio de reposo");
+}
+
+#[test]
+fn asteroids_collide_bulge_to_bulge() {
+    // Los círculos de reposo están separados, los contornos estirados se solapan
+    let gap = 50.0;
+    let mut world = empty_world(without_merging());
+    world.asteroids.push(stretched((CENTER.0 - gap / 2.0, CENTER.1)));
+    world.asteroids.push(stretched((CENTER.0 + gap / 2.0, CENTER.1)));
+    world.step(1.0 / 120.0, &Input::default());
+
+    let distance = wrapped_distance(world.asteroids[0].pos, world.asteroids[1].pos);
+    assert!(distance > 2.0 * SIZE * STRETCH - 0.5, "siguen solapados a {}", distance);
+
+    let mut world = empty_world(without_merging());
+    world.asteroids.push(Asteroid::new((CENTER.0 - gap / 2.0, CENTER.1), (0.0, 0.0), SIZE));
+    world.asteroids.push(Asteroid::new((CENTER.0 + gap / 2.0, CENTER.1), (0.0, 0.0), SIZE));
+    world.step(1.0 / 120.0, &Input::default());
+
+    let distance = wrapped_distance(world.asteroids[0].pos, world.asteroids[1].pos);
+    assert!((distance - gap).abs() < 0.5, "han chocado sin tocarse: {}", distance);
+}
+
+#[test]
+fn point_in_a_dent_is_outside() {
+    // Cuadrado con una muesca en el lado derecho
+    let dented = [(-10.0, -10.0), (10.0, -10.0), (10.0, -2.0), (0.0, 0.0), (10.0, 2.0), (10.0, 10.0), (-10.0, 10.0)];
+    assert!(point_in_polygon((-5.0, 0.0), &dented));
+    assert!(point_in_polygon((8.0, 6.0), &dented));
+    assert!(!point_in_polygon((8.0, 0.0), &dented));
+    assert!(!point_in_polygon((12.0, 0.0), &dented));
+}
@@ -0,0 +1,177 @@
+mod common;
+
+use bubbleroid::{fragment, wrapped_delta, Asteroid, Bullet, Config, Input};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use common::{empty_world, without_merging};
+
+// Casos aleatorios por propiedad
+const CASES: u64 = 200;
+
+fn random_split(rng: &mut ChaCha8Rng, config: &Config) -> (Asteroid, Bullet, Vec<Asteroid>) {
+    let pos = (rng.gen_range(0.0..1280.0), rng.gen_range(0.0..720.0));
+    let vel = (rng.gen_range(-100.0..100.0), rng.gen_range(-100.0..100.0));
+    let size = rng.gen_range(config.asteroids.split_size + 1.0..120.0);
+    let mut asteroid = Asteroid::jagged(pos, vel, size, config.asteroids.jaggedness, rng);
+    asteroid.angular_vel = rng.gen_range(-3.0..3.0);
+
+    let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+    let hit = (pos.0 + angle.cos() * size * 0.9, pos.1 + angle.sin() * size * 0.9);
+    let bullet = Bullet::new(hit, (-angle.cos() * 400.0, -angle.sin() * 400.0), 1.0);
+
+    let fragments = fragment(&asteroid, &bullet, config, rng);
+    (asteroid, bullet, fragments)
+}
+
+fn momentum(asteroids: &[Asteroid]) -> (f32, f32) {
+    asteroids
+        .iter()
+        .fold((0.0, 0.0), |sum, asteroid| (sum.0 + asteroid.mass * asteroid.vel.0, sum.1 + asteroid.mass * asteroid.vel.1))
+}
+
+#[test]
+fn split_conserves_mass() {
+    let config = Config::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+    for _ in 0..CASES {
+        let (asteroid, _, fragments) = random_split(&mut rng, &config);
+        assert!(fragments.len() >= 2);
+        let mass: f32 = fragments.iter().map(|fragment| fragment.mass).sum();
+        assert!((mass - asteroid.mass).abs() < asteroid.mass * 1e-4, "{} frente a {}", mass, asteroid.mass);
+    }
+}
+
+#[test]
+fn split_conserves_momentum_with_the_bullet() {
+    let config = Config::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(2);
+    for _ in 0..CASES {
+        let (asteroid, bullet, fragments) = random_split(&mut rng, &config);
+        let before = (
+            asteroid.mass * asteroid.vel.0 + bullet.mass() * bullet.vel.0,
+            asteroid.mass * asteroid.vel.1 + bullet.mass() * bullet.vel.1,
+        );
+        let after = momentum(&fragments);
+        let scale = asteroid.mass * (100.0 + config.asteroids.fragment_speed);
+        assert!(
+            (after.0 - before.0).abs() < scale * 1e-4 && (after.1 - before.1).abs() < scale * 1e-4,
+            "{:?} frente a {:?}",
+            after,
+            before
+        );
+    }
+}
+
+#[test]
+fn split_keeps_the_center_of_mass() {
+    let config = Config::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(3);
+    for _ in 0..CASES {
+        let (asteroid, _, fragments) = random_split(&mut rng, &config);
+        let center = fragments.iter().fold((0.0, 0.0), |sum, fragment| {
+            let (dx, dy) = wrapped_delta(asteroid.pos, fragment.pos);
+            (sum.0 + fragment.mass * dx, sum.1 + fragment.mass * dy)
+        });
+        assert!(center.0.abs() < asteroid.mass * 1e-2 && center.1.abs() < asteroid.mass * 1e-2, "{:?}", center);
+    }
+}
+
+#[test]
+fn fragments_do_not_overlap() {
+    let config = Config::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(4);
+    for _ in 0..CASES {
+        let (_, _, fragments) = random_split(&mut rng, &config);
+        for (i, first) in fragments.iter().enumerate() {
+            for second in &fragments[i + 1..] {
+                assert!(!first.overlaps(second), "trozos solapados en {:?} y {:?}", first.pos, second.pos);
+            }
+        }
+    }
+}
+
+#[test]
+fn fragments_separate_and_carry_the_bullet_impulse() {
+    let config = Config::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(5);
+    // La bala llega por la izquierda moviéndose hacia +x
+    let asteroid = Asteroid::new((600.0, 360.0), (0.0, 0.0), 40.0);
+    let bullet = Bullet::new((565.0, 360.0), (400.0, 0.0), 1.0);
+    let fragments = fragment(&asteroid, &bullet, &config, &mut rng);
+
+    let (px, py) = momentum(&fragments);
+    assert!(px > 0.0 && py.abs() < px * 1e-3, "momento {:?}", (px, py));
+    // Cada pareja de trozos se separa
+    for (i, first) in fragments.iter().enumerate() {
+        for second in &fragments[i + 1..] {
+            let (dx, dy) = wrapped_delta(first.pos, second.pos);
+            let (dvx, dvy) = (second.vel.0 - first.vel.0, second.vel.1 - first.vel.1);
+            assert!(dx * dvx + dy * dvy > 0.0, "dos trozos se acercan");
+        }
+    }
+}
+
+#[test]
+fn bigger_asteroids_break_into_more_pieces() {
+    let config = Config::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(6);
+    let bullet = Bullet::new((600.0, 360.0), (400.0, 0.0), 1.0);
+    let count = |size: f32, rng: &mut ChaCha8Rng| {
+        fragment(&Asteroid::new((600.0, 360.0), (0.0, 0.0), size), &bullet, &config, rng).len()
+    };
+
+    assert_eq!(count(config.asteroids.split_size, &mut rng), 0);
+    assert_eq!(count(21.0, &mut rng), 2);
+    assert_eq!(count(30.0, &mut rng), 3);
+    assert_eq!(count(40.0, &mut rng), config.asteroids.max_fragments);
+    assert_eq!(count(400.0, &mut rng), config.asteroids.max_fragments);
+}
+
+#[test]
+fn world_split_conserves_mass() {
+    let mut world = empty_world(Config::default());
+    let asteroid = Asteroid::new((600.0, 360.0), (0.0, 0.0), 40.0);
+    let mass = asteroid.mass;
+    world.asteroids.push(asteroid);
+    world.bullets.push(Bullet::new((590.0, 360.0), (400.0, 0.0), 1.0));
+    world.step(1.0 / 120.0, &Input::default());
+
+    assert!(world.bullets.is_empty());
+    assert!(world.asteroids.len() >= 2);
+    let total: f32 = world.asteroids.iter().map(|asteroid| asteroid.mass).sum();
+    assert!((total - mass).abs() < mass * 1e-4, "{} frente a {}", total, mass);
+}
+
+#[test]
+fn bullet_splits_only_one_of_two_overlapping_asteroids() {
+    let mut config = without_merging();
+    config.physics.gravity_constant = 0.0;
+    let mut world = empty_world(config);
+    // Tres asteroides en el mismo punto: al separarlos por parejas, los que
+    // acaban en 540 y 590 siguen solapados, y la bala queda dentro de los dos
+    for _ in 0..3 {
+        world.asteroids.push(Asteroid::new((600.0, 360.0), (0.0, 0.0), 40.0));
+    }
+    let bullet = Bullet::new((560.0, 360.0), (400.0, 0.0), 1.0);
+    let impulse = (bullet.mass() * bullet.vel.0, bullet.mass() * bullet.vel.1);
+    world.bullets.push(bullet);
+    let mass: f32 = world.asteroids.iter().map(|asteroid| asteroid.mass).sum();
+    world.step(1.0 / 120.0, &Input::default());
+
+    assert!(world.bullets.is_empty());
+    let whole = world.asteroids.iter().filter(|asteroid| asteroid.size == 40.0).count();
+    assert_eq!(whole, 2, "la bala ha partido {} asteroides", 3 - whole);
+    let total: f32 = world.asteroids.iter().map(|asteroid| asteroid.mass).sum();
+    assert!((total - mass).abs() < mass * 1e-4);
+
+    // Estaban quietos: todo el momento viene de un solo impulso de bala
+    let change = momentum(&world.asteroids);
+    let tolerance = mass * 1e-3;
+    assert!(
+        (change.0 - impulse.0).abs() < tolerance && (change.1 - impulse.1).abs() < tolerance,
+        "el momento ha cambiado {:?} en vez de {:?}",
+        change,
+        impulse
+    );
+}
@@ -0,0 +1,87 @@
+use bubbleroid::constants::{WINDOW_HEIGHT, WINDOW_WIDTH};
+use bubbleroid::{barnes_hut_gravity, exact_gravity, gravity_forces, Asteroid, GravitySolver, PhysicsConfig};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+fn random_asteroids(count: usize, seed: u64) -> Vec<Asteroid> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    (0..count)
+        .map(|_| {
+            let pos = (rng.gen_range(0.0..WINDOW_WIDTH), rng.gen_range(0.0..WINDOW_HEIGHT));
+            Asteroid::new(pos, (0.0, 0.0), rng.gen_range(15.0..40.0))
+        })
+        .collect()
+}
+
+fn length(v: (f32, f32)) -> f32 {
+    (v.0 * v.0 + v.1 * v.1).sqrt()
+}
+
+// Error de cada fuerza relativo a la fuerza media, para que los asteroides en
+// equilibrio (fuerza casi nula) no disparen el error relativo
+fn relative_errors(approx: &[(f32, f32)], exact: &[(f32, f32)]) -> Vec<f32> {
+    let mean = exact.iter().map(|&force| length(force)).sum::<f32>() / exact.len() as f32;
+    approx
+        .iter()
+        .zip(exact)
+        .map(|(a, e)| length((a.0 - e.0, a.1 - e.1)) / mean)
+        .collect()
+}
+
+#[test]
+fn barnes_hut_matches_exact_within_tolerance() {
+    // Los asteroides aleatorios se solapan. Con el suavizado por defecto las
+    // fuerzas entre vecinos muy cercanos casi desaparecen, la fuerza media cae
+    // y el error relativo a ella deja de medir la aproximación: se compara casi
+    // sin suavizado
+    let physics = PhysicsConfig {
+        softening: 0.5,
+        ..PhysicsConfig::default()
+    };
+    let asteroids = random_asteroids(2000, 1);
+
+    let exact = exact_gravity(&physics, &asteroids);
+    let approx = barnes_hut_gravity(&physics, &asteroids, physics.barnes_hut_theta);
+    let errors = relative_errors(&approx, &exact);
+
+    let mean_error = errors.iter().sum::<f32>() / errors.len() as f32;
+    let max_error = errors.iter().copied().fold(0.0, f32::max);
+    println!("error medio {mean_error}, máximo {max_error}");
+    assert!(mean_error < 0.01, "error medio {mean_error}");
+    assert!(max_error < 0.1, "error máximo {max_error}");
+}
+
+#[test]
+fn barnes_hut_with_zero_theta_is_exact() {
+    let physics = PhysicsConfig::default();
+    let asteroids = random_asteroids(300, 2);
+
+    let exact = exact_gravity(&physics, &asteroids);
+    let approx = barnes_hut_gravity(&physics, &asteroids, 0.0);
+    let max_error = relative_errors(&approx, &exact).into_iter().fold(0.0, f32::max);
+    assert!(max_error < 1e-4, "error máximo {max_error}");
+}
+
+#[test]
+fn barnes_hut_handles_coincident_asteroids() {
+    let physics = PhysicsConfig::default();
+    let mut asteroids = random_asteroids(10, 3);
+    for _ in 0..5 {
+        asteroids.push(Asteroid::new((100.0, 100.0), (0.0, 0.0), 20.0));
+    }
+
+    let forces = barnes_hut_gravity(&physics, &asteroids, 0.5);
+    assert!(forces.iter().all(|force| force.0.is_finite() && force.1.is_finite()));
+}
+
+#[test]
+fn small_counts_use_the_exact_solver() {
+    let physics = PhysicsConfig {
+        gravity_solver: GravitySolver::BarnesHut,
+        barnes_hut_threshold: 200,
+        ..PhysicsConfig::default()
+    };
+    let asteroids = random_asteroids(199, 4);
+
+    assert_eq!(gravity_forces(&physics, &asteroids), exact_gravity(&physics, &asteroids));
+}
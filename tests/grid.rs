@@ -0,0 +1,85 @@
+use bubbleroid::constants::{WINDOW_HEIGHT, WINDOW_WIDTH};
+use bubbleroid::{wrapped_distance, SpatialGrid};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+// Casos aleatorios por propiedad
+const CASES: u64 = 100;
+
+// Posiciones que incluyen los casos difíciles: pegadas a cada borde y a cada
+// esquina, a los dos lados
+fn random_position(rng: &mut ChaCha8Rng) -> (f32, f32) {
+    let near = |rng: &mut ChaCha8Rng, length: f32| {
+        if rng.gen() {
+            rng.gen_range(0.0..10.0)
+        } else {
+            length - rng.gen_range(1e-3..10.0)
+        }
+    };
+    let x = rng.gen_range(0.0..WINDOW_WIDTH);
+    let y = rng.gen_range(0.0..WINDOW_HEIGHT);
+    match rng.gen_range(0..4) {
+        0 => (near(rng, WINDOW_WIDTH), y),
+        1 => (x, near(rng, WINDOW_HEIGHT)),
+        2 => (near(rng, WINDOW_WIDTH), near(rng, WINDOW_HEIGHT)),
+        _ => (x, y),
+    }
+}
+
+#[test]
+fn grid_finds_every_pair_brute_force_does() {
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+    let mut grid = SpatialGrid::default();
+    let mut candidates = Vec::new();
+    for _ in 0..CASES {
+        let count = rng.gen_range(1..200);
+        // A veces objetos tan grandes que una consulta cubre todo un eje
+        let max_radius = if rng.gen_range(0..5) == 0 { 400.0 } else { 40.0 };
+        let bodies: Vec<((f32, f32), f32)> =
+            (0..count).map(|_| (random_position(&mut rng), rng.gen_range(1.0..max_radius))).collect();
+        let max_size = bodies.iter().map(|body| body.1).fold(0.0, f32::max);
+        grid.build(2.0 * max_size, bodies.iter().map(|body| body.0));
+
+        for (i, &(pos, size)) in bodies.iter().enumerate() {
+            grid.query_sorted(pos, size + max_size, &mut candidates);
+            assert!(candidates.windows(2).all(|pair| pair[0] < pair[1]), "candidatos repetidos: {:?}", candidates);
+
+            let touching = bodies
+                .iter()
+                .enumerate()
+                .filter(|&(_, &(other, other_size))| wrapped_distance(pos, other) < size + other_size)
+                .map(|(j, _)| j);
+            for j in touching {
+                assert!(
+                    candidates.binary_search(&j).is_ok(),
+                    "la rejilla no devuelve {:?} junto a {:?} (objeto {})",
+                    bodies[j].0,
+                    pos,
+                    i
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn query_reaches_across_every_edge_and_corner() {
+    let mut grid = SpatialGrid::default();
+    let (w, h) = (WINDOW_WIDTH, WINDOW_HEIGHT);
+    // Uno en cada esquina: a través de los bordes están todos a 2 px
+    let corners = [(1.0, 1.0), (w - 1.0, 1.0), (1.0, h - 1.0), (w - 1.0, h - 1.0)];
+    grid.build(10.0, corners.into_iter());
+
+    let mut candidates = Vec::new();
+    for corner in corners {
+        grid.query_sorted(corner, 10.0, &mut candidates);
+        assert_eq!(candidates, [0, 1, 2, 3], "desde {:?}", corner);
+    }
+    // Y en mitad de cada borde, el de enfrente
+    let edges = [(1.0, h / 2.0), (w - 1.0, h / 2.0), (w / 2.0, 1.0), (w / 2.0, h - 1.0)];
+    grid.build(10.0, edges.into_iter());
+    for (i, opposite) in [(0, 1), (1, 0), (2, 3), (3, 2)] {
+        grid.query_sorted(edges[i], 10.0, &mut candidates);
+        assert_eq!(candidates, [i.min(opposite), i.max(opposite)], "desde {:?}", edges[i]);
+    }
+}
@@ -0,0 +1,67 @@
+use bubbleroid::{integrate, Asteroid, Diagnostics, Integrator, PhysicsConfig};
+
+const TICKS: u64 = 3000;
+const DT: f32 = 1.0 / 120.0;
+
+// Deriva relativa máxima de la energía y momento máximo a lo largo de una
+// órbita excéntrica de dos asteroides de los tamaños dados, sin límites de
+// aceleración
+fn orbit(integrator: Integrator, sizes: (f32, f32)) -> (f64, f64) {
+    let physics = PhysicsConfig {
+        gravity_scale: 1e11,
+        integrator,
+        ..PhysicsConfig::default()
+    };
+    let mut asteroids = vec![
+        Asteroid::new((590.0, 360.0), (0.0, 0.0), sizes.0),
+        Asteroid::new((690.0, 360.0), (0.0, 0.0), sizes.1),
+    ];
+    // Un 70% de la velocidad de la órbita circular. Con masa inercial 1 los dos
+    // aceleran igual y giran en torno al punto medio aunque pesen distinto.
+    let (distance, softening) = (100.0_f32, physics.softening);
+    let force = physics.gravity_constant * physics.gravity_scale * asteroids[0].mass * asteroids[1].mass * distance
+        / (distance * distance + softening * softening).powf(1.5);
+    let speed = 0.7 * (force * distance / 2.0).sqrt();
+    asteroids[0].vel = (0.0, -speed);
+    asteroids[1].vel = (0.0, speed);
+
+    let start = Diagnostics::measure(0, &physics, &asteroids).total_energy();
+    let (mut drift, mut momentum) = (0.0_f64, 0.0_f64);
+    for tick in 1..=TICKS {
+        integrate(&physics, f32::INFINITY, &mut asteroids, DT);
+        let diagnostics = Diagnostics::measure(tick, &physics, &asteroids);
+        drift = drift.max(((diagnostics.total_energy() - start) / start).abs());
+        momentum = momentum.max(diagnostics.momentum.0.abs().max(diagnostics.momentum.1.abs()));
+    }
+    (drift, momentum)
+}
+
+#[test]
+fn every_integrator_conserves_momentum() {
+    for integrator in [Integrator::SemiImplicitEuler, Integrator::VelocityVerlet, Integrator::Rk4] {
+        let (_, momentum) = orbit(integrator, (20.0, 20.0));
+        assert!(momentum < 1e-3, "{:?}: momento {}", integrator, momentum);
+    }
+}
+
+#[test]
+fn unequal_masses_conserve_momentum_and_energy() {
+    // Uno pesa 9 veces más que el otro
+    for integrator in [Integrator::SemiImplicitEuler, Integrator::VelocityVerlet, Integrator::Rk4] {
+        let (drift, momentum) = orbit(integrator, (30.0, 10.0));
+        assert!(momentum < 1e-3, "{:?}: momento {}", integrator, momentum);
+        assert!(drift < 0.05, "{:?}: deriva de la energía {}", integrator, drift);
+    }
+}
+
+#[test]
+fn higher_order_integrators_drift_less() {
+    let (euler, _) = orbit(Integrator::SemiImplicitEuler, (20.0, 20.0));
+    let (verlet, _) = orbit(Integrator::VelocityVerlet, (20.0, 20.0));
+    let (rk4, _) = orbit(Integrator::Rk4, (20.0, 20.0));
+    println!("deriva de la energía: euler {euler}, verlet {verlet}, rk4 {rk4}");
+
+    assert!(euler < 0.05, "euler {}", euler);
+    assert!(verlet < 1e-3 && verlet < euler / 10.0, "verlet {}", verlet);
+    assert!(rk4 < 1e-3 && rk4 < euler / 10.0, "rk4 {}", rk4);
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use bubbleroid::constants::TICK_DT;
+use bubbleroid::{wrapped_distance, Asteroid, Bullet, Config, Input, World};
+use common::empty_world;
+
+const SIZE: f32 = 30.0;
+
+// Sin gravedad, para que los asteroides se queden donde se ponen
+fn still_world(config: Config) -> World {
+    let mut config = config;
+    config.physics.gravity_constant = 0.0;
+    empty_world(config)
+}
+
+// Pone un asteroide encima de la nave y avanza un tick
+fn crash(world: &mut World) {
+    world.asteroids.push(Asteroid::new(world.player_pos, (0.0, 0.0), SIZE));
+    world.step(TICK_DT, &Input::default());
+}
+
+#[test]
+fn lives_count_down_to_game_over() {
+    let mut config = Config::default();
+    config.player.lives = 3;
+    config.player.invulnerability_time = 0.0;
+    let mut world = still_world(config);
+
+    for lives in (0..3).rev() {
+        crash(&mut world);
+        assert_eq!(world.lives, lives);
+        assert_eq!(world.is_game_over, lives == 0);
+    }
+
+    // Acabada la partida, la simulación se para
+    let tick = world.tick;
+    let pos = world.player_pos;
+    crash(&mut world);
+    assert_eq!(world.lives, 0);
+    assert_eq!(world.tick, tick + 1);
+    assert_eq!(world.player_pos, pos);
+}
+
+#[test]
+fn ship_respawns_clear_of_asteroids() {
+    let config = Config::default();
+    let radius = config.player.respawn_safe_radius;
+    let mut world = still_world(config);
+    // El centro, primer sitio donde se prueba, está ocupado
+    world.asteroids.push(Asteroid::new((640.0, 360.0), (0.0, 0.0), SIZE));
+    world.asteroids.push(Asteroid::new((760.0, 360.0), (0.0, 0.0), SIZE));
+    crash(&mut world);
+
+    assert_eq!(world.lives, world.config.player.lives - 1);
+    for asteroid in &world.asteroids {
+        let clearance = wrapped_distance(world.player_pos, asteroid.pos) - asteroid.bounding_radius();
+        assert!(clearance >= radius, "la nave reaparece a {} de un asteroide en {:?}", clearance, asteroid.pos);
+    }
+    assert_eq!(world.player_vel, (0.0, 0.0));
+}
+
+#[test]
+fn invulnerability_stops_hits_until_it_runs_out() {
+    let mut config = Config::default();
+    config.player.invulnerability_time = 0.5;
+    let mut world = still_world(config);
+    crash(&mut world);
+    let lives = world.lives;
+    assert!(world.is_invulnerable());
+
+    // Un asteroide encima de la nave no le hace nada mientras dura
+    world.asteroids.push(Asteroid::new(world.player_pos, (0.0, 0.0), SIZE));
+    let mut ticks = 0u32;
+    while world.lives == lives {
+        assert!(world.is_invulnerable(), "la nave ha chocado siendo invulnerable");
+        world.step(TICK_DT, &Input::default());
+        ticks += 1;
+    }
+    assert_eq!(world.lives, lives - 1);
+    let expected = (0.5 / TICK_DT).round() as u32;
+    assert!(ticks.abs_diff(expected) <= 1, "choca tras {} ticks, se esperaban {}", ticks, expected);
+}
+
+#[test]
+fn extra_life_at_every_threshold() {
+    let mut config = Config::default();
+    config.player.extra_life_score = 100;
+    let mut world = still_world(config);
+    let lives = world.lives;
+
+    world.score = 99;
+    world.step(TICK_DT, &Input::default());
+    assert_eq!(world.lives, lives);
+
+    world.score = 100;
+    world.step(TICK_DT, &Input::default());
+    assert_eq!(world.lives, lives + 1);
+
+    // Un salto grande pasa varios umbrales de una vez
+    world.score = 450;
+    world.step(TICK_DT, &Input::default());
+    assert_eq!(world.lives, lives + 4);
+    assert_eq!(world.next_extra_life, 500);
+}
+
+#[test]
+fn bullet_hit_counts_on_the_tick_the_ship_crashes() {
+    let mut world = still_world(Config::default());
+    world.asteroids.push(Asteroid::new((640.0, 360.0), (0.0, 0.0), SIZE));
+    world.bullets.push(Bullet::new((640.0, 360.0), (0.0, 0.0), 1.0));
+    let lives = world.lives;
+    crash(&mut world);
+
+    assert_eq!(world.lives, lives - 1);
+    assert!(world.bullets.is_empty(), "la bala no se ha gastado");
+    assert!(world.score > 0, "el acierto no ha sumado puntos");
+    assert_eq!(world.destroyed_count, 1.0);
+}
@@ -0,0 +1,83 @@
+use bubbleroid::constants::TICK_DT;
+use bubbleroid::{Config, Input, Recorder, Replay, ReplayError, World, REPLAY_VERSION};
+
+const TICKS: u64 = 600;
+
+// Controles que cambian a menudo: gira, empuja y dispara a ratos
+fn input_at(tick: u64) -> Input {
+    Input {
+        thrust: tick % 90 < 40,
+        rotate_left: tick % 150 < 30,
+        rotate_right: tick % 200 > 170,
+        fire: tick.is_multiple_of(25),
+    }
+}
+
+// Juega una partida grabándola y devuelve el estado final con su repetición
+fn record_run() -> (World, Replay) {
+    let seed = 42;
+    let config = Config::default();
+    let mut world = World::new(seed, config.clone());
+    let mut recorder = Recorder::new(seed, config);
+    for tick in 0..TICKS {
+        let input = input_at(tick);
+        recorder.record(world.tick, &input);
+        world.step(TICK_DT, &input);
+    }
+    (world, recorder.finish())
+}
+
+fn to_bytes(replay: &Replay) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    replay.write_to(&mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn round_trip_reproduces_the_run() {
+    let (original, replay) = record_run();
+    let loaded = Replay::read_from(&mut to_bytes(&replay).as_slice()).unwrap();
+    assert_eq!(loaded, replay);
+
+    let played = loaded.play();
+    assert_eq!(played.tick, original.tick);
+    assert_eq!(played.score, original.score);
+    assert_eq!(played.lives, original.lives);
+    let positions = |world: &World| world.asteroids.iter().map(|asteroid| asteroid.pos).collect::<Vec<_>>();
+    assert_eq!(positions(&played), positions(&original));
+}
+
+#[test]
+fn other_version_is_rejected() {
+    let (_, replay) = record_run();
+    let mut bytes = to_bytes(&replay);
+    // La versión va justo detrás de la firma de cuatro bytes
+    bytes[4..6].copy_from_slice(&(REPLAY_VERSION + 1).to_le_bytes());
+    match Replay::read_from(&mut bytes.as_slice()) {
+        Err(ReplayError::UnsupportedVersion(version)) => assert_eq!(version, REPLAY_VERSION + 1),
+        other => panic!("se esperaba UnsupportedVersion y llegó {:?}", other),
+    }
+}
+
+#[test]
+fn truncated_file_is_corrupt() {
+    let (_, replay) = record_run();
+    let bytes = to_bytes(&replay);
+    for len in [10, bytes.len() / 2, bytes.len() - 1] {
+        match Replay::read_from(&mut &bytes[..len]) {
+            Err(ReplayError::Corrupt(_)) => {}
+            other => panic!("{} bytes: se esperaba Corrupt y llegó {:?}", len, other),
+        }
+    }
+}
+
+#[test]
+fn two_events_on_the_same_tick_are_corrupt() {
+    let (_, mut replay) = record_run();
+    let duplicate = replay.events[1];
+    replay.events.insert(1, duplicate);
+    match Replay::read_from(&mut to_bytes(&replay).as_slice()) {
+        Err(ReplayError::Corrupt(_)) => {}
+        other => panic!("se esperaba Corrupt y llegó {:?}", other),
+    }
+}
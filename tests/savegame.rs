@@ -0,0 +1,80 @@
+use bubbleroid::constants::TICK_DT;
+use bubbleroid::{Config, Input, Recorder, SaveError, SaveGame, World};
+
+const SEED: u64 = 7;
+// Ticks jugados antes de guardar y después de cargar
+const BEFORE: u64 = 400;
+const AFTER: u64 = 400;
+
+// Controles que cambian a menudo: gira, empuja y dispara a ratos
+fn input_at(tick: u64) -> Input {
+    Input {
+        thrust: tick % 80 < 30,
+        rotate_left: tick % 130 < 25,
+        rotate_right: tick % 170 > 140,
+        fire: tick.is_multiple_of(20),
+    }
+}
+
+fn play(world: &mut World, recorder: &mut Recorder, ticks: u64) {
+    for _ in 0..ticks {
+        let input = input_at(world.tick);
+        recorder.record(world.tick, &input);
+        world.step(TICK_DT, &input);
+    }
+}
+
+// Guarda la partida en memoria tal como lo hace el juego
+fn to_bytes(world: &World, recorder: &Recorder) -> Vec<u8> {
+    let save = SaveGame { world: world.clone(), replay: recorder.finish() };
+    let mut bytes = Vec::new();
+    save.write_to(&mut bytes).unwrap();
+    bytes
+}
+
+// El estado serializado lo cubre todo, generador aleatorio incluido
+fn state(world: &World) -> String {
+    toml::to_string(world).unwrap()
+}
+
+#[test]
+fn loaded_game_continues_like_the_original() {
+    let mut original = World::new(SEED, Config::default());
+    let mut recorder = Recorder::new(SEED, Config::default());
+    play(&mut original, &mut recorder, BEFORE);
+
+    let save = SaveGame::read_from(&mut to_bytes(&original, &recorder).as_slice()).unwrap();
+    assert_eq!(state(&save.world), state(&original));
+    let mut loaded = save.world;
+    let mut resumed = Recorder::resume(save.replay);
+
+    play(&mut original, &mut recorder, AFTER);
+    play(&mut loaded, &mut resumed, AFTER);
+    assert_eq!(loaded.tick, BEFORE + AFTER);
+    assert_eq!(state(&loaded), state(&original), "la partida cargada se ha separado de la original");
+
+    // La repetición seguida tras cargar reproduce la partida entera
+    assert_eq!(state(&resumed.finish().play()), state(&original));
+}
+
+#[test]
+fn save_with_a_foreign_replay_is_rejected() {
+    let mut world = World::new(SEED, Config::default());
+    let mut recorder = Recorder::new(SEED, Config::default());
+    play(&mut world, &mut recorder, BEFORE);
+
+    let mut other_ticks = recorder.finish();
+    other_ticks.ticks += 1;
+    let mut other_seed = recorder.finish();
+    other_seed.seed += 1;
+
+    for replay in [other_ticks, other_seed] {
+        let mut bytes = Vec::new();
+        SaveGame { world: world.clone(), replay }.write_to(&mut bytes).unwrap();
+        match SaveGame::read_from(&mut bytes.as_slice()) {
+            Err(SaveError::Corrupt(_)) => {}
+            Err(err) => panic!("error inesperado: {}", err),
+            Ok(_) => panic!("se ha aceptado una repetición que no corresponde a la partida"),
+        }
+    }
+}
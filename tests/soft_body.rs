@@ -0,0 +1,82 @@
+use bubbleroid::constants::TICK_DT;
+use bubbleroid::{Asteroid, PhysicsConfig, Vertex};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+fn jagged() -> Asteroid {
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+    Asteroid::jagged((600.0, 360.0), (0.0, 0.0), 40.0, 0.25, &mut rng)
+}
+
+fn length(v: (f32, f32)) -> f32 {
+    (v.0 * v.0 + v.1 * v.1).sqrt()
+}
+
+// Lo que se ha apartado el vértice de su posición de reposo
+fn displacement(vertex: &Vertex) -> f32 {
+    length((vertex.offset.0 - vertex.rest.0, vertex.offset.1 - vertex.rest.1))
+}
+
+// Coseno entre la dirección de reposo del vértice y `direction`
+fn facing(vertex: &Vertex, direction: (f32, f32)) -> f32 {
+    (vertex.rest.0 * direction.0 + vertex.rest.1 * direction.1) / length(vertex.rest)
+}
+
+#[test]
+fn dent_moves_only_the_side_that_was_hit() {
+    // Girado un cuarto de vuelta: un golpe desde +y da en el lado +x del cuerpo
+    let mut asteroid = jagged();
+    asteroid.angle = std::f32::consts::FRAC_PI_2;
+    asteroid.dent((0.0, 1.0), 50.0);
+    asteroid.update_deformation(TICK_DT, &PhysicsConfig::default());
+
+    let body_direction = (1.0, 0.0);
+    for vertex in &asteroid.vertices {
+        let facing = facing(vertex, body_direction);
+        if facing <= 0.0 {
+            assert_eq!(vertex.vel, (0.0, 0.0), "se ha movido un vértice del otro lado: {:?}", vertex.rest);
+            assert_eq!(vertex.offset, vertex.rest);
+        } else if facing > 0.9 {
+            assert!(length(vertex.offset) < length(vertex.rest), "el vértice de {:?} no se ha hundido", vertex.rest);
+        }
+    }
+
+    // El más hundido es el que mira al golpe
+    let deepest = asteroid
+        .vertices
+        .iter()
+        .max_by(|a, b| {
+            let sink = |vertex: &Vertex| length(vertex.rest) - length(vertex.offset);
+            sink(a).total_cmp(&sink(b))
+        })
+        .unwrap();
+    assert!(facing(deepest, body_direction) > 0.9, "el más hundido está en {:?}", deepest.rest);
+}
+
+#[test]
+fn ring_springs_back_to_its_jagged_shape() {
+    let physics = PhysicsConfig::default();
+    let mut asteroid = jagged();
+    let rest: Vec<(f32, f32)> = asteroid.vertices.iter().map(|vertex| vertex.rest).collect();
+    // La forma de reposo no es un círculo
+    let radii: Vec<f32> = rest.iter().map(|&point| length(point)).collect();
+    let spread = radii.iter().cloned().fold(0.0, f32::max) - radii.iter().cloned().fold(f32::INFINITY, f32::min);
+    assert!(spread > 2.0, "el asteroide es casi redondo");
+
+    asteroid.dent((1.0, 0.0), 80.0);
+    asteroid.wobble(20.0);
+    for _ in 0..10 {
+        asteroid.update_deformation(TICK_DT, &physics);
+    }
+    assert!(asteroid.vertices.iter().any(|vertex| displacement(vertex) > 0.5), "el golpe no ha deformado el contorno");
+
+    // Un minuto de simulación
+    for _ in 0..(60.0 / TICK_DT) as usize {
+        asteroid.update_deformation(TICK_DT, &physics);
+    }
+    for (vertex, rest) in asteroid.vertices.iter().zip(rest) {
+        assert_eq!(vertex.rest, rest);
+        assert!(displacement(vertex) < 1e-2, "el vértice de {:?} sigue a {} de su sitio", rest, displacement(vertex));
+        assert!(length(vertex.vel) < 1e-2);
+    }
+}
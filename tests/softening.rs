@@ -0,0 +1,152 @@
+use bubbleroid::constants::{WINDOW_HEIGHT, WINDOW_WIDTH};
+use bubbleroid::{barnes_hut_gravity, calculate_gravity, exact_gravity, Asteroid, Config, Input, PhysicsConfig, World};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+// Casos aleatorios por propiedad
+const CASES: u64 = 200;
+
+fn is_finite(v: (f32, f32)) -> bool {
+    v.0.is_finite() && v.1.is_finite()
+}
+
+// Posiciones que incluyen los casos difíciles: puntos repetidos, sobre el
+// borde y pegados al otro lado
+fn random_position(rng: &mut ChaCha8Rng, previous: &[Asteroid]) -> (f32, f32) {
+    match rng.gen_range(0..4) {
+        0 if !previous.is_empty() => previous[rng.gen_range(0..previous.len())].pos,
+        1 => (0.0, rng.gen_range(0.0..WINDOW_HEIGHT)),
+        2 => (WINDOW_WIDTH - f32::EPSILON * WINDOW_WIDTH, WINDOW_HEIGHT - 1e-3),
+        _ => (rng.gen_range(0.0..WINDOW_WIDTH), rng.gen_range(0.0..WINDOW_HEIGHT)),
+    }
+}
+
+fn random_asteroids(rng: &mut ChaCha8Rng, max_count: usize) -> Vec<Asteroid> {
+    let count = rng.gen_range(2..max_count);
+    let mut asteroids = Vec::with_capacity(count);
+    for _ in 0..count {
+        let pos = random_position(rng, &asteroids);
+        asteroids.push(Asteroid::new(pos, (0.0, 0.0), rng.gen_range(1.0..200.0)));
+    }
+    asteroids
+}
+
+fn random_physics(rng: &mut ChaCha8Rng) -> PhysicsConfig {
+    PhysicsConfig {
+        softening: rng.gen_range(1e-3..50.0),
+        gravity_scale: rng.gen_range(0.0..1e10),
+        ..PhysicsConfig::default()
+    }
+}
+
+#[test]
+fn pair_force_is_finite_and_bounded() {
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+    for _ in 0..CASES * 50 {
+        let physics = random_physics(&mut rng);
+        let (mass1, mass2) = (rng.gen_range(0.0..1e4), rng.gen_range(0.0..1e4));
+        let delta = match rng.gen_range(0..3) {
+            0 => (0.0, 0.0),
+            1 => (rng.gen_range(-1e-3..1e-3), rng.gen_range(-1e-3..1e-3)),
+            _ => (rng.gen_range(-1e4..1e4), rng.gen_range(-1e4..1e4)),
+        };
+        let force = calculate_gravity(&physics, mass1, mass2, delta);
+        assert!(is_finite(force), "{:?} con {:?}", force, delta);
+
+        // El máximo de r / (r² + ε²)^(3/2) está en r = ε / √2
+        let peak = physics.gravity_constant * mass1 * mass2 * physics.gravity_scale * 2.0
+            / (3.0 * 3f32.sqrt() * physics.softening * physics.softening);
+        let magnitude = (force.0 * force.0 + force.1 * force.1).sqrt();
+        assert!(magnitude <= peak * 1.001, "{} supera el máximo {}", magnitude, peak);
+    }
+}
+
+#[test]
+fn coincident_bodies_feel_no_force_from_each_other() {
+    let physics = PhysicsConfig::default();
+    assert_eq!(calculate_gravity(&physics, 100.0, 100.0, (0.0, 0.0)), (0.0, 0.0));
+}
+
+#[test]
+fn solver_forces_are_finite_for_any_positions() {
+    let mut rng = ChaCha8Rng::seed_from_u64(2);
+    for _ in 0..CASES {
+        let physics = random_physics(&mut rng);
+        let asteroids = random_asteroids(&mut rng, 300);
+        let theta = rng.gen_range(0.0..1.0);
+        for force in exact_gravity(&physics, &asteroids).into_iter().chain(barnes_hut_gravity(&physics, &asteroids, theta)) {
+            assert!(is_finite(force), "{:?}", force);
+        }
+    }
+}
+
+#[test]
+fn world_stays_finite_for_any_positions() {
+    let mut rng = ChaCha8Rng::seed_from_u64(3);
+    for _ in 0..CASES / 4 {
+        let mut config = Config::default();
+        config.asteroids.count = 0;
+        config.physics = random_physics(&mut rng);
+        config.bullets.gravity = true;
+        let mut world = World::new(rng.gen(), config);
+        world.asteroids = random_asteroids(&mut rng, 60);
+        // La nave justo en el centro de un asteroide
+        world.player_pos = world.asteroids[0].pos;
+        world.invulnerable_time = 1.0;
+
+        for tick in 0..20 {
+            let input = Input {
+                fire: tick % 10 == 0,
+                ..Input::default()
+            };
+            world.step(1.0 / 120.0, &input);
+            assert!(is_finite(world.player_pos) && is_finite(world.player_vel));
+            assert!(world.asteroids.iter().all(|asteroid| is_finite(asteroid.pos) && is_finite(asteroid.vel)));
+            assert!(world.bullets.iter().all(|bullet| is_finite(bullet.pos) && is_finite(bullet.vel)));
+        }
+    }
+}
+
+#[test]
+fn acceleration_limit_keeps_the_direction() {
+    let mut config = Config::default();
+    config.asteroids.count = 0;
+    config.asteroids.max_acceleration = 0.1;
+    let mut world = World::new(1, config);
+    // Lejos de la diagonal: limitar cada eje por separado la torcería a 45°
+    world.asteroids.push(Asteroid::new((600.0, 360.0), (0.0, 0.0), 20.0));
+    world.asteroids.push(Asteroid::new((660.0, 380.0), (0.0, 0.0), 20.0));
+    world.step(1.0 / 120.0, &Input::default());
+
+    let vel = world.asteroids[0].vel;
+    let speed = (vel.0 * vel.0 + vel.1 * vel.1).sqrt();
+    assert!((speed - 0.1 / 120.0).abs() < 1e-6, "velocidad {}", speed);
+    assert!((vel.1 / vel.0 - 20.0 / 60.0).abs() < 1e-3, "dirección {:?}", vel);
+}
+
+#[test]
+fn ship_gravity_is_limited_on_an_asteroid() {
+    let mut rng = ChaCha8Rng::seed_from_u64(4);
+    for _ in 0..CASES / 4 {
+        let mut config = Config::default();
+        config.asteroids.count = 0;
+        config.physics = random_physics(&mut rng);
+        config.physics.player_gravity_factor = rng.gen_range(0.0..1e3);
+        config.asteroids.max_acceleration = rng.gen_range(1.0..500.0);
+        let mut world = World::new(rng.gen(), config);
+        world.asteroids = random_asteroids(&mut rng, 10);
+        // La nave a un pelo del centro de un asteroide, donde más tira
+        let center = world.asteroids[0].pos;
+        world.player_pos = (center.0 + rng.gen_range(-1.0..1.0), center.1 + rng.gen_range(-1.0..1.0));
+        world.invulnerable_time = f32::INFINITY;
+
+        // Con el rozamiento, la velocidad que puede acumular la gravedad limitada
+        let dt = 1.0 / 120.0;
+        let limit = world.config.asteroids.max_acceleration * dt / (1.0 - world.config.player.friction);
+        for _ in 0..120 {
+            world.step(dt, &Input::default());
+            let speed = (world.player_vel.0.powi(2) + world.player_vel.1.powi(2)).sqrt();
+            assert!(speed.is_finite() && speed <= limit * 1.001, "velocidad {} con límite {}", speed, limit);
+        }
+    }
+}
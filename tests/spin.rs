@@ -0,0 +1,101 @@
+mod common;
+
+use bubbleroid::{Asteroid, Bullet, Config, Input, World};
+use common::empty_world;
+
+const SIZE: f32 = 30.0;
+
+// Sin gravedad, para que solo cuenten los choques
+fn without_gravity() -> World {
+    let mut config = Config::default();
+    config.physics.gravity_constant = 0.0;
+    empty_world(config)
+}
+
+fn momentum(world: &World) -> (f32, f32) {
+    world
+        .asteroids
+        .iter()
+        .fold((0.0, 0.0), |sum, asteroid| (sum.0 + asteroid.mass * asteroid.vel.0, sum.1 + asteroid.mass * asteroid.vel.1))
+}
+
+// Un asteroide quieto y otro que llega desde la derecha a `offset` px por encima
+fn collide(offset: f32) -> World {
+    let mut world = without_gravity();
+    world.asteroids.push(Asteroid::new((600.0, 360.0), (0.0, 0.0), SIZE));
+    world.asteroids.push(Asteroid::new((600.0 + 1.6 * SIZE, 360.0 + offset), (-150.0, 0.0), SIZE));
+    world.step(1.0 / 120.0, &Input::default());
+    world
+}
+
+#[test]
+fn inertia_of_a_round_asteroid_is_close_to_a_disc() {
+    let asteroid = Asteroid::new((0.0, 0.0), (0.0, 0.0), SIZE);
+    let disc = asteroid.mass * SIZE * SIZE / 2.0;
+    assert!((asteroid.inertia - disc).abs() < disc * 0.05, "{} frente a {}", asteroid.inertia, disc);
+}
+
+#[test]
+fn head_on_hit_does_not_spin() {
+    let world = collide(0.0);
+    assert!(world.asteroids.iter().all(|asteroid| asteroid.angular_vel.abs() < 1e-4));
+}
+
+#[test]
+fn glancing_hit_spins_both_asteroids() {
+    let world = collide(0.8 * SIZE);
+    let (first, second) = (&world.asteroids[0], &world.asteroids[1]);
+    assert!(first.angular_vel.abs() > 0.1, "el primero no gira: {}", first.angular_vel);
+    assert!(second.angular_vel.abs() > 0.1, "el segundo no gira: {}", second.angular_vel);
+    // El rozamiento empuja en sentidos opuestos a lados opuestos de los centros:
+    // los dos giran en el mismo sentido
+    assert_eq!(first.angular_vel.signum(), second.angular_vel.signum());
+
+    // El impulso entre ellos no cambia el momento lineal total
+    let expected = 150.0 * world.asteroids[1].mass;
+    let (px, py) = momentum(&world);
+    assert!((px + expected).abs() < expected * 1e-3 && py.abs() < expected * 1e-3, "{:?}", (px, py));
+}
+
+#[test]
+fn frictionless_hit_does_not_spin() {
+    let mut world = without_gravity();
+    world.config.physics.friction = 0.0;
+    world.asteroids.push(Asteroid::new((600.0, 360.0), (0.0, 0.0), SIZE));
+    world.asteroids.push(Asteroid::new((600.0 + 1.6 * SIZE, 360.0 + 0.8 * SIZE), (-150.0, 0.0), SIZE));
+    world.step(1.0 / 120.0, &Input::default());
+    assert!(world.asteroids.iter().all(|asteroid| asteroid.angular_vel.abs() < 1e-4));
+}
+
+#[test]
+fn spin_turns_the_outline() {
+    let mut world = without_gravity();
+    let mut asteroid = Asteroid::new((600.0, 360.0), (0.0, 0.0), SIZE);
+    asteroid.angular_vel = 1.2;
+    world.asteroids.push(asteroid);
+    for _ in 0..120 {
+        world.step(1.0 / 120.0, &Input::default());
+    }
+
+    let asteroid = &world.asteroids[0];
+    assert!((asteroid.angle - 1.2).abs() < 1e-3, "ángulo {}", asteroid.angle);
+    // El primer vértice, en reposo sobre +x, ha girado con el cuerpo
+    let first = asteroid.outline()[0];
+    let turned = (first.1 - asteroid.pos.1).atan2(first.0 - asteroid.pos.0);
+    assert!((turned - 1.2).abs() < 1e-2, "el contorno está a {} rad", turned);
+}
+
+#[test]
+fn fragments_inherit_spin() {
+    let mut world = without_gravity();
+    let mut asteroid = Asteroid::new((600.0, 360.0), (0.0, 0.0), SIZE);
+    asteroid.angular_vel = 2.0;
+    world.asteroids.push(asteroid);
+    world.bullets.push(Bullet::new((600.0, 360.0), (0.0, 0.0), 1.0));
+    world.step(1.0 / 120.0, &Input::default());
+
+    assert!(!world.asteroids.is_empty());
+    for fragment in &world.asteroids {
+        assert!((fragment.angular_vel - 2.0).abs() < 1e-4, "el trozo gira a {}", fragment.angular_vel);
+    }
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use bubbleroid::constants::{WINDOW_HEIGHT, WINDOW_WIDTH};
+use bubbleroid::{
+    barnes_hut_gravity, exact_gravity, wrapped_delta, wrapped_distance, Asteroid, Bullet, Input, PhysicsConfig,
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use common::{empty_world, without_merging};
+
+// Separación entre los dos lados del borde en cada caso
+const GAP: f32 = 8.0;
+
+// Dos puntos a lados opuestos de cada borde: izquierdo, derecho, superior e inferior
+fn across_edges() -> [((f32, f32), (f32, f32)); 4] {
+    let (w, h) = (WINDOW_WIDTH, WINDOW_HEIGHT);
+    let near = GAP / 2.0;
+    [
+        ((near, h / 2.0), (w - near, h / 2.0)),
+        ((w - near, h / 2.0), (near, h / 2.0)),
+        ((w / 2.0, near), (w / 2.0, h - near)),
+        ((w / 2.0, h - near), (w / 2.0, near)),
+    ]
+}
+
+fn inside_window(pos: (f32, f32)) -> bool {
+    (0.0..WINDOW_WIDTH).contains(&pos.0) && (0.0..WINDOW_HEIGHT).contains(&pos.1)
+}
+
+#[test]
+fn wrapped_delta_takes_the_short_way_around() {
+    for (a, b) in across_edges() {
+        let (dx, dy) = wrapped_delta(a, b);
+        assert!((dx.abs() + dy.abs() - GAP).abs() < 1e-3, "{:?} -> {:?}: {:?}", a, b, (dx, dy));
+        assert!((wrapped_distance(a, b) - GAP).abs() < 1e-3);
+    }
+    // Lejos de los bordes no cambia nada
+    assert_eq!(wrapped_delta((100.0, 100.0), (300.0, 250.0)), (200.0, 150.0));
+}
+
+#[test]
+fn gravity_pulls_across_each_edge() {
+    let physics = PhysicsConfig::default();
+    let centered = exact_gravity(
+        &physics,
+        &[Asteroid::new((600.0, 300.0), (0.0, 0.0), 20.0), Asteroid::new((600.0 + GAP, 300.0), (0.0, 0.0), 20.0)],
+    );
+    let expected = centered[0].0;
+
+    for (a, b) in across_edges() {
+        let asteroids = [Asteroid::new(a, (0.0, 0.0), 20.0), Asteroid::new(b, (0.0, 0.0), 20.0)];
+        let (dx, dy) = wrapped_delta(a, b);
+        let direction = (dx / GAP, dy / GAP);
+        let forces = [exact_gravity(&physics, &asteroids), barnes_hut_gravity(&physics, &asteroids, 0.5)];
+        for force in forces {
+            let along = force[0].0 * direction.0 + force[0].1 * direction.1;
+            assert!((along - expected).abs() <= expected * 1e-3, "{:?} -> {:?}: {:?}", a, b, force);
+            assert!((force[0].0 + force[1].0).abs() < 1e-3 && (force[0].1 + force[1].1).abs() < 1e-3);
+        }
+    }
+}
+
+#[test]
+fn barnes_hut_matches_exact_near_the_edges() {
+    let physics = PhysicsConfig::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(7);
+    // Asteroides pegados a los cuatro bordes, que se atraen sobre todo dando la vuelta
+    let asteroids: Vec<Asteroid> = (0..1000)
+        .map(|i| {
+            let offset = rng.gen_range(-40.0..40.0_f32);
+            let along = rng.gen_range(0.0..1.0_f32);
+            let pos = if i % 2 == 0 {
+                (offset.rem_euclid(WINDOW_WIDTH), along * WINDOW_HEIGHT)
+            } else {
+                (along * WINDOW_WIDTH, offset.rem_euclid(WINDOW_HEIGHT))
+            };
+            Asteroid::new(pos, (0.0, 0.0), rng.gen_range(15.0..40.0))
+        })
+        .collect();
+
+    let exact = exact_gravity(&physics, &asteroids);
+    let approx = barnes_hut_gravity(&physics, &asteroids, physics.barnes_hut_theta);
+    let mean = exact.iter().map(|f| (f.0 * f.0 + f.1 * f.1).sqrt()).sum::<f32>() / exact.len() as f32;
+    for (a, e) in approx.iter().zip(&exact) {
+        let error = ((a.0 - e.0).powi(2) + (a.1 - e.1).powi(2)).sqrt() / mean;
+        assert!(error < 0.1, "error {} con fuerza exacta {:?}", error, e);
+    }
+}
+
+#[test]
+fn asteroids_collide_across_each_edge() {
+    for (a, b) in across_edges() {
+        let mut world = empty_world(without_merging());
+        world.asteroids.push(Asteroid::new(a, (0.0, 0.0), 20.0));
+        world.asteroids.push(Asteroid::new(b, (0.0, 0.0), 20.0));
+        world.step(1.0 / 120.0, &Input::default());
+
+        let (first, second) = (&world.asteroids[0], &world.asteroids[1]);
+        assert!(
+            wrapped_distance(first.pos, second.pos) >= 40.0 - 1e-3,
+            "{:?} -> {:?}: siguen solapados en {:?} y {:?}",
+            a,
+            b,
+            first.pos,
+            second.pos
+        );
+        assert!(inside_window(first.pos) && inside_window(second.pos));
+    }
+}
+
+#[test]
+fn bullets_hit_across_each_edge() {
+    for (a, b) in across_edges() {
+        let mut world = empty_world(without_merging());
+        world.asteroids.push(Asteroid::new(b, (0.0, 0.0), 20.0));
+        world.bullets.push(Bullet::new(a, (0.0, 0.0), 1.0));
+        world.step(1.0 / 120.0, &Input::default());
+
+        assert!(world.asteroids.is_empty(), "{:?} -> {:?}: el asteroide sigue ahí", a, b);
+        assert!(world.bullets.is_empty());
+        assert!(world.score > 0);
+    }
+}
+
+#[test]
+fn player_is_hit_across_each_edge() {
+    for (a, b) in across_edges() {
+        let mut world = empty_world(without_merging());
+        let lives = world.lives;
+        world.player_pos = a;
+        world.asteroids.push(Asteroid::new(b, (0.0, 0.0), 20.0));
+        world.step(1.0 / 120.0, &Input::default());
+
+        assert_eq!(world.lives, lives - 1, "{:?} -> {:?}: la nave no ha chocado", a, b);
+    }
+}
+
+#[test]
+fn player_wraps_any_displacement() {
+    // Más de dos pantallas en un tick, hacia atrás y hacia delante
+    for (screens, expected) in [(-2.5, (650.0, 370.0)), (3.5, (650.0, 370.0))] {
+        let mut world = empty_world(without_merging());
+        world.player_pos = (10.0, 10.0);
+        world.player_vel = (screens * WINDOW_WIDTH * 120.0, screens * WINDOW_HEIGHT * 120.0);
+        world.step(1.0 / 120.0, &Input::default());
+
+        let pos = world.player_pos;
+        assert!(inside_window(pos), "la nave se ha salido a {:?}", pos);
+        assert!(wrapped_distance(pos, expected) < 0.1, "{:?} en vez de {:?}", pos, expected);
+    }
+}